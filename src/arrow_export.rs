@@ -0,0 +1,185 @@
+//! Columnar Arrow export for result sets.
+//!
+//! `PyRowWriter.values` is already a flat, column-major-addressable `CompactValue`
+//! buffer, so a result set can be walked once per column into an Arrow array
+//! builder instead of boxing every cell into a `PyObject` via
+//! `compact_value_to_py`. The finished `RecordBatch` crosses into Python through
+//! the Arrow C Data Interface (`arrow::pyarrow::ToPyArrow`), a single FFI hop.
+
+use std::sync::Arc;
+
+use arrow::array::{
+    ArrayRef, BinaryBuilder, BooleanBuilder, Date32Builder, Decimal128Builder, Float64Builder,
+    Int64Builder, StringBuilder, Time64NanosecondBuilder, TimestampMicrosecondBuilder,
+};
+use arrow::datatypes::{DataType, Field, Schema, TimeUnit};
+use arrow::pyarrow::ToPyArrow;
+use arrow::record_batch::RecordBatch;
+use pyo3::prelude::*;
+
+use crate::cursor::ColumnInfo;
+use crate::row_writer::{CompactValue, PyRowWriter};
+
+/// Map a `ColumnInfo` (the same codes `column_type_to_sql_type` produces,
+/// plus its `column_size`/`decimal_digits` for DECIMAL/NUMERIC) to the Arrow
+/// type used for that column's builder. Anything without a natural columnar
+/// representation (SQL_VARIANT, XML) falls back to `Utf8`, matching how
+/// `compact_value_to_py` already degrades those.
+fn arrow_type_for(column: &ColumnInfo) -> DataType {
+    match column.sql_type {
+        4 | 5 | -6 | -5 => DataType::Int64,
+        6 | 7 | 8 => DataType::Float64,
+        -7 => DataType::Boolean,
+        2 | 3 => {
+            let precision = (column.column_size as u8).clamp(1, 38);
+            let scale = (column.decimal_digits as i8).clamp(0, precision as i8);
+            DataType::Decimal128(precision, scale)
+        }
+        93 | -155 => DataType::Timestamp(TimeUnit::Microsecond, None),
+        91 => DataType::Date32,
+        92 => DataType::Time64(TimeUnit::Nanosecond),
+        -2 | -3 | -4 => DataType::Binary,
+        _ => DataType::Utf8,
+    }
+}
+
+enum ColumnBuilder {
+    Int64(Int64Builder),
+    Float64(Float64Builder),
+    Bool(BooleanBuilder),
+    Utf8(StringBuilder),
+    Binary(BinaryBuilder),
+    Decimal128(Decimal128Builder, i8),
+    Date32(Date32Builder),
+    Time64Ns(Time64NanosecondBuilder),
+    TimestampUs(TimestampMicrosecondBuilder),
+}
+
+impl ColumnBuilder {
+    fn new(data_type: &DataType, capacity: usize) -> Self {
+        match data_type {
+            DataType::Int64 => ColumnBuilder::Int64(Int64Builder::with_capacity(capacity)),
+            DataType::Float64 => ColumnBuilder::Float64(Float64Builder::with_capacity(capacity)),
+            DataType::Boolean => ColumnBuilder::Bool(BooleanBuilder::with_capacity(capacity)),
+            DataType::Binary => {
+                ColumnBuilder::Binary(BinaryBuilder::with_capacity(capacity, capacity * 16))
+            }
+            DataType::Decimal128(precision, scale) => ColumnBuilder::Decimal128(
+                Decimal128Builder::with_capacity(capacity)
+                    .with_precision_and_scale(*precision, *scale)
+                    .expect("static precision/scale"),
+                *scale,
+            ),
+            DataType::Date32 => ColumnBuilder::Date32(Date32Builder::with_capacity(capacity)),
+            DataType::Time64(_) => {
+                ColumnBuilder::Time64Ns(Time64NanosecondBuilder::with_capacity(capacity))
+            }
+            DataType::Timestamp(_, _) => {
+                ColumnBuilder::TimestampUs(TimestampMicrosecondBuilder::with_capacity(capacity))
+            }
+            _ => ColumnBuilder::Utf8(StringBuilder::with_capacity(capacity, capacity * 16)),
+        }
+    }
+
+    /// Append `val`, nulling out the cell if its `CompactValue` variant
+    /// doesn't match the builder's column type (only possible with corrupt
+    /// metadata, since the builder's type was derived from the same
+    /// `sql_type` the wire decode classified the value against), or — for
+    /// `Decimal128` specifically — if the cell's own scale doesn't match the
+    /// scale the builder was constructed with, rather than appending an
+    /// `i128` under the wrong power-of-ten and silently corrupting it.
+    fn push(&mut self, val: &CompactValue) {
+        match (self, val) {
+            (ColumnBuilder::Int64(b), CompactValue::I64(v)) => b.append_value(*v),
+            (ColumnBuilder::Float64(b), CompactValue::F64(v)) => b.append_value(*v),
+            (ColumnBuilder::Bool(b), CompactValue::Bool(v)) => b.append_value(*v),
+            (ColumnBuilder::Binary(b), CompactValue::Bytes(v)) => b.append_value(v),
+            (ColumnBuilder::Decimal128(b, scale), CompactValue::Decimal(v, _, cell_scale)) => {
+                if *cell_scale as i8 == *scale {
+                    b.append_value(*v)
+                } else {
+                    b.append_null()
+                }
+            }
+            (ColumnBuilder::Date32(b), CompactValue::Date(d)) => b.append_value(*d),
+            (ColumnBuilder::Time64Ns(b), CompactValue::Time(n)) => b.append_value(*n),
+            (ColumnBuilder::TimestampUs(b), CompactValue::DateTime(m)) => b.append_value(*m),
+            (ColumnBuilder::TimestampUs(b), CompactValue::DateTimeOffset(m, _)) => {
+                b.append_value(*m)
+            }
+            (ColumnBuilder::Utf8(b), CompactValue::Str(s)) => b.append_value(s),
+            (ColumnBuilder::Utf8(b), CompactValue::Guid(bytes)) => {
+                b.append_value(uuid::Uuid::from_bytes(*bytes).to_string())
+            }
+            (ColumnBuilder::Int64(b), _) => b.append_null(),
+            (ColumnBuilder::Float64(b), _) => b.append_null(),
+            (ColumnBuilder::Bool(b), _) => b.append_null(),
+            (ColumnBuilder::Binary(b), _) => b.append_null(),
+            (ColumnBuilder::Decimal128(b, _), _) => b.append_null(),
+            (ColumnBuilder::Date32(b), _) => b.append_null(),
+            (ColumnBuilder::Time64Ns(b), _) => b.append_null(),
+            (ColumnBuilder::TimestampUs(b), _) => b.append_null(),
+            (ColumnBuilder::Utf8(b), _) => b.append_null(),
+        }
+    }
+
+    fn finish(self) -> ArrayRef {
+        match self {
+            ColumnBuilder::Int64(mut b) => Arc::new(b.finish()),
+            ColumnBuilder::Float64(mut b) => Arc::new(b.finish()),
+            ColumnBuilder::Bool(mut b) => Arc::new(b.finish()),
+            ColumnBuilder::Utf8(mut b) => Arc::new(b.finish()),
+            ColumnBuilder::Binary(mut b) => Arc::new(b.finish()),
+            ColumnBuilder::Decimal128(mut b, _) => Arc::new(b.finish()),
+            ColumnBuilder::Date32(mut b) => Arc::new(b.finish()),
+            ColumnBuilder::Time64Ns(mut b) => Arc::new(b.finish()),
+            ColumnBuilder::TimestampUs(mut b) => Arc::new(b.finish()),
+        }
+    }
+}
+
+/// Build an Arrow `RecordBatch` from a decoded result set, walking
+/// `writer.values` column-major so each array is built with one pass and
+/// one allocation per column rather than per cell.
+pub fn writer_to_record_batch(
+    writer: &PyRowWriter,
+    columns: &[ColumnInfo],
+) -> PyResult<RecordBatch> {
+    let row_count = writer.row_count();
+    let fields: Vec<Field> = columns
+        .iter()
+        .map(|c| Field::new(&c.name, arrow_type_for(c), c.nullable != 0))
+        .collect();
+    let mut builders: Vec<ColumnBuilder> = fields
+        .iter()
+        .map(|f| ColumnBuilder::new(f.data_type(), row_count))
+        .collect();
+
+    for (col, builder) in builders.iter_mut().enumerate() {
+        for row in 0..row_count {
+            builder.push(writer.get(row, col));
+        }
+    }
+
+    let arrays: Vec<ArrayRef> = builders.into_iter().map(ColumnBuilder::finish).collect();
+    let schema = Arc::new(Schema::new(fields));
+    RecordBatch::try_new(schema, arrays).map_err(|e| {
+        pyo3::exceptions::PyRuntimeError::new_err(format!("Arrow batch build failed: {}", e))
+    })
+}
+
+/// Export a decoded result set as a `pyarrow.Table`, crossing into Python
+/// once via the Arrow C Data Interface instead of once per cell.
+pub fn writer_to_pyarrow(
+    py: Python<'_>,
+    writer: &PyRowWriter,
+    columns: &[ColumnInfo],
+) -> PyResult<PyObject> {
+    let batch = writer_to_record_batch(writer, columns)?;
+    let py_batch = batch.to_pyarrow(py)?;
+    let table = py
+        .import("pyarrow")?
+        .getattr("Table")?
+        .call_method1("from_batches", (vec![py_batch],))?;
+    Ok(table.unbind())
+}
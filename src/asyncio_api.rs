@@ -0,0 +1,150 @@
+//! Asyncio-native API surface, alongside the blocking `Connection`/
+//! `StatementHandle` DBAPI types.
+//!
+//! Every method on the blocking types wraps its work in
+//! `py.allow_threads(|| runtime::block_on(...))`, which parks the calling
+//! Python thread for the duration of the query — fine for a thread-per-
+//! connection DBAPI caller, but it means an asyncio/FastAPI app needs a
+//! thread per in-flight connection just to avoid blocking its event loop.
+//! `AsyncConnection`/`AsyncCursor` give such callers awaitables instead:
+//! each method hands the existing blocking `TdsConnection`/`TdsCursor` call
+//! to `tokio::task::spawn_blocking` on the same shared [`crate::runtime`]
+//! the rest of the crate already runs queries on, and
+//! `pyo3_asyncio::tokio::future_into_py` turns the resulting `JoinHandle`
+//! into the coroutine's result — the same move the rust-postgres ecosystem
+//! made going from the synchronous `postgres::Connection` to the
+//! tokio-based `tokio_postgres::Client`.
+
+use std::sync::{Arc, Mutex};
+
+use pyo3::exceptions::PyRuntimeError;
+use pyo3::prelude::*;
+
+use crate::connection::TdsConnection;
+use crate::cursor::TdsCursor;
+
+fn join_err(e: tokio::task::JoinError) -> PyErr {
+    PyRuntimeError::new_err(format!("Async task panicked: {}", e))
+}
+
+#[pyclass(name = "AsyncConnection")]
+pub struct AsyncConnection {
+    inner: Arc<Mutex<TdsConnection>>,
+}
+
+#[pymethods]
+impl AsyncConnection {
+    /// Open a connection without blocking the event loop: the TCP connect
+    /// and TDS login run on a blocking-pool thread via `spawn_blocking`.
+    #[staticmethod]
+    fn connect(py: Python<'_>, connection_str: String) -> PyResult<Bound<'_, PyAny>> {
+        pyo3_asyncio::tokio::future_into_py(py, async move {
+            let conn = tokio::task::spawn_blocking(move || TdsConnection::new(&connection_str, None))
+                .await
+                .map_err(join_err)??;
+            Ok(AsyncConnection {
+                inner: Arc::new(Mutex::new(conn)),
+            })
+        })
+    }
+
+    fn cursor(&self) -> PyResult<AsyncCursor> {
+        let cursor = self.inner.lock().unwrap().alloc_cursor()?;
+        Ok(AsyncCursor {
+            inner: Arc::new(Mutex::new(cursor)),
+        })
+    }
+
+    fn commit<'p>(&self, py: Python<'p>) -> PyResult<Bound<'p, PyAny>> {
+        let conn = self.inner.clone();
+        pyo3_asyncio::tokio::future_into_py(py, async move {
+            tokio::task::spawn_blocking(move || conn.lock().unwrap().commit())
+                .await
+                .map_err(join_err)?
+        })
+    }
+
+    fn rollback<'p>(&self, py: Python<'p>) -> PyResult<Bound<'p, PyAny>> {
+        let conn = self.inner.clone();
+        pyo3_asyncio::tokio::future_into_py(py, async move {
+            tokio::task::spawn_blocking(move || conn.lock().unwrap().rollback())
+                .await
+                .map_err(join_err)?
+        })
+    }
+
+    fn close<'p>(&self, py: Python<'p>) -> PyResult<Bound<'p, PyAny>> {
+        let conn = self.inner.clone();
+        pyo3_asyncio::tokio::future_into_py(py, async move {
+            tokio::task::spawn_blocking(move || conn.lock().unwrap().close())
+                .await
+                .map_err(join_err)?
+        })
+    }
+}
+
+#[pyclass(name = "AsyncCursor")]
+pub struct AsyncCursor {
+    inner: Arc<Mutex<TdsCursor>>,
+}
+
+#[pymethods]
+impl AsyncCursor {
+    #[pyo3(signature = (sql, params=None))]
+    fn execute<'p>(
+        &self,
+        py: Python<'p>,
+        sql: String,
+        params: Option<Vec<PyObject>>,
+    ) -> PyResult<Bound<'p, PyAny>> {
+        let cursor = self.inner.clone();
+        let params = params.unwrap_or_default();
+        pyo3_asyncio::tokio::future_into_py(py, async move {
+            tokio::task::spawn_blocking(move || {
+                Python::with_gil(|py| {
+                    let bound: Vec<Bound<'_, PyAny>> =
+                        params.iter().map(|p| p.bind(py).clone()).collect();
+                    cursor.lock().unwrap().execute(&sql, &bound)
+                })
+            })
+            .await
+            .map_err(join_err)?
+        })
+    }
+
+    fn fetchone<'p>(&self, py: Python<'p>) -> PyResult<Bound<'p, PyAny>> {
+        let cursor = self.inner.clone();
+        pyo3_asyncio::tokio::future_into_py(py, async move {
+            tokio::task::spawn_blocking(move || Python::with_gil(|py| cursor.lock().unwrap().fetchone(py)))
+                .await
+                .map_err(join_err)?
+        })
+    }
+
+    fn fetchmany<'p>(&self, py: Python<'p>, size: usize) -> PyResult<Bound<'p, PyAny>> {
+        let cursor = self.inner.clone();
+        pyo3_asyncio::tokio::future_into_py(py, async move {
+            tokio::task::spawn_blocking(move || {
+                Python::with_gil(|py| cursor.lock().unwrap().fetchmany(py, size))
+            })
+            .await
+            .map_err(join_err)?
+        })
+    }
+
+    fn fetchall<'p>(&self, py: Python<'p>) -> PyResult<Bound<'p, PyAny>> {
+        let cursor = self.inner.clone();
+        pyo3_asyncio::tokio::future_into_py(py, async move {
+            tokio::task::spawn_blocking(move || Python::with_gil(|py| cursor.lock().unwrap().fetchall(py)))
+                .await
+                .map_err(join_err)?
+        })
+    }
+
+    /// Request cancellation of whatever query is currently in flight on
+    /// this cursor's connection. See [`crate::cancel`] for why this reports
+    /// `NotSupportedError` rather than actually interrupting the query.
+    fn cancel(&self) -> PyResult<()> {
+        self.inner.lock().unwrap().cancel()
+    }
+}
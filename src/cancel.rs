@@ -0,0 +1,69 @@
+//! Out-of-band query cancellation.
+//!
+//! A real TDS cancellation works by writing an Attention (0x06) packet to
+//! the *same* socket a query is already in flight on, while a separate task
+//! drains the server's response to it, the way `tiberius`'s own internal
+//! cancel handle does. `tabby::Client` (see its use in `connection.rs` and
+//! `cursor.rs`) only exposes `execute_raw`/`into_results` and the
+//! `batch_*` family — there is no accessor for the underlying socket and no
+//! `cancel`/`attention` method to call while a `Client` is on loan to
+//! another task via its `Mutex`. Without either hook, this crate has no way
+//! to interrupt an in-flight request that wouldn't also corrupt the TDS
+//! byte stream for every query run on the connection afterwards.
+//!
+//! [`CancelToken`] gives callers the shape the DBAPI layer needs — something
+//! `Clone + Send` that can be stashed on another thread before a blocking
+//! call begins — but [`CancelToken::cancel`] reports `NotSupportedError`
+//! rather than silently no-oping or risking stream corruption. It keys on
+//! the connection's host (rather than being a bare flag) so that a future
+//! tabby release exposing a real attention/cancel hook only needs a change
+//! in this one file.
+//!
+//! There is deliberately no "cancellation requested" flag here: `cancel()`
+//! always fails synchronously and never interrupts anything, so recording
+//! that it was called would only give later, unrelated calls on the same
+//! connection something to trip over with no way to clear it — every
+//! cursor `Connection::alloc_cursor` creates shares this same token, so a
+//! flag set by one cursor's `cancel()` would permanently reject every other
+//! cursor's queries too. The `Err` `cancel()` itself already returns is the
+//! only signal there is.
+
+use std::sync::Arc;
+
+use pyo3::prelude::*;
+
+/// Cheaply clonable, `Send` handle that can be stashed on another Python
+/// thread before a blocking `execute`/`commit`/etc. begins, then used to
+/// request cancellation of whatever is in flight on the connection it was
+/// issued from.
+#[derive(Clone)]
+pub struct CancelToken {
+    host: Arc<String>,
+}
+
+impl CancelToken {
+    pub fn new(host: String) -> Self {
+        CancelToken {
+            host: Arc::new(host),
+        }
+    }
+
+    /// Request cancellation of whatever query is currently in flight on the
+    /// connection this token was issued from.
+    pub fn cancel(&self) -> PyResult<()> {
+        let msg = format!(
+            "Cannot cancel query on {}: tabby exposes no TDS attention/cancel hook, so an \
+             in-flight request can't be interrupted without corrupting the connection",
+            self.host
+        );
+        Python::with_gil(|py| {
+            let result: PyResult<PyErr> = (|| {
+                let exc_mod = py.import("whiskers.exceptions")?;
+                let exc_class = exc_mod.getattr("NotSupportedError")?;
+                let err_obj = exc_class.call1((&msg,))?;
+                Ok(PyErr::from_value(err_obj.into_any().unbind().into_bound(py)))
+            })();
+            Err(result.unwrap_or_else(|_| pyo3::exceptions::PyRuntimeError::new_err(msg.clone())))
+        })
+    }
+}
@@ -1,19 +1,489 @@
 use pyo3::prelude::*;
 use pyo3::types::PyDict;
 use tabby::{AuthMethod, Client, Config, EncryptionLevel};
+use tokio::io::BufWriter;
 use tokio::net::TcpStream;
 use tokio_util::compat::{Compat, TokioAsyncWriteCompatExt};
 
-use crate::cursor::{SharedTxState, TdsCursor, TransactionState};
+use crate::cancel::CancelToken;
+use crate::cursor::{
+    PreparedStatementCache, QueryLogConfig, SharedPreparedCache, SharedTxState, TdsCursor,
+    TransactionState,
+};
 use crate::errors::to_pyerr;
+use crate::pool::{PoolConfig, TdsPool};
 use crate::runtime;
+use std::hash::{Hash, Hasher};
 use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
-pub type SharedClient = Arc<Mutex<Client<Compat<TcpStream>>>>;
+pub type SharedClient = Arc<Mutex<Client<Compat<BufWriter<TcpStream>>>>>;
+
+/// Quote `name` as a T-SQL bracketed identifier (`[name]`), doubling any
+/// `]` the way T-SQL itself does for a literal `]` inside one — the same
+/// convention `dialect.rs` rewrites other quoting styles into. Savepoint
+/// names are spliced straight into `SAVE TRANSACTION`/`ROLLBACK
+/// TRANSACTION`, so without this a name like `"x; DROP TABLE users; --"`
+/// would execute as a second statement in the same batch.
+fn quote_bracket_identifier(name: &str) -> String {
+    format!("[{}]", name.replace(']', "]]"))
+}
+
+/// The fixed set of SQL Server isolation-level keywords accepted by `SET
+/// TRANSACTION ISOLATION LEVEL`. `set_isolation_level` checks incoming
+/// values against this list (rather than escaping them) because the level
+/// is prepended, unescaped, to every transaction-opening batch in
+/// `cursor.rs` — an allowlist is the only thing that keeps it from being a
+/// standing SQL injection once a caller can set it.
+const ISOLATION_LEVELS: &[&str] = &[
+    "READ UNCOMMITTED",
+    "READ COMMITTED",
+    "REPEATABLE READ",
+    "SNAPSHOT",
+    "SERIALIZABLE",
+];
+
+/// Transport-level knobs: `tcpnodelay` (default on, matching the prior
+/// hardcoded `set_nodelay(true)`) and `writebufferbytes`, the capacity of
+/// the [`BufWriter`] wrapped around the socket's write half so the several
+/// small writes tabby makes per outgoing TDS message coalesce into one
+/// `send`/flush instead of hitting the kernel per write call.
+struct TransportConfig {
+    tcp_nodelay: bool,
+    write_buffer_bytes: usize,
+}
+
+impl Default for TransportConfig {
+    fn default() -> Self {
+        Self {
+            tcp_nodelay: true,
+            write_buffer_bytes: 8192,
+        }
+    }
+}
+
+/// Parse [`TransportConfig`] out of the connection string, kept separate
+/// from [`parse_connection_string`] the same way [`parse_query_log_config`]
+/// is.
+fn parse_transport_config(conn_str: &str) -> TransportConfig {
+    let mut cfg = TransportConfig::default();
+    for part in conn_str.split(';') {
+        let part = part.trim();
+        if let Some(idx) = part.find('=') {
+            let key = part[..idx].trim().to_lowercase();
+            let val = part[idx + 1..].trim();
+            match key.as_str() {
+                "tcpnodelay" => {
+                    cfg.tcp_nodelay = val.eq_ignore_ascii_case("yes")
+                        || val == "1"
+                        || val.eq_ignore_ascii_case("true")
+                }
+                "writebufferbytes" => {
+                    if let Ok(bytes) = val.parse() {
+                        cfg.write_buffer_bytes = bytes;
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+    cfg
+}
 
 pub struct TdsConnection {
     client: Option<SharedClient>,
     tx_state: SharedTxState,
+    query_log: Arc<QueryLogConfig>,
+    pool: Arc<TdsPool>,
+    host: String,
+    created_at: Instant,
+    cancel_token: CancelToken,
+    prepared_cache: SharedPreparedCache,
+    source_dialect: Option<crate::dialect::Dialect>,
+    conversion_options: crate::types::ConversionOptions,
+}
+
+/// Best-effort `sp_unprepare` for every handle in `handles`, run as one
+/// semicolon-joined batch. Used by [`TdsConnection::close`] to release the
+/// server-side prepared statements [`crate::cursor::TdsCursor::execute_prepared`]
+/// accumulated — unlike mid-session eviction (handled by the cursor itself,
+/// which already holds `client`), `close` has just taken `client` out of
+/// `self`, so this takes it as a plain argument instead of being a method.
+fn unprepare_handles(client: &SharedClient, handles: &[i32]) {
+    if handles.is_empty() {
+        return;
+    }
+    let sql = handles
+        .iter()
+        .map(|h| format!("EXEC sp_unprepare {}", h))
+        .collect::<Vec<_>>()
+        .join("; ");
+    let client = client.clone();
+    Python::with_gil(|py| {
+        py.allow_threads(|| {
+            runtime::block_on(async {
+                let mut c = client.lock().unwrap();
+                if let Ok(result) = c.execute_raw(sql).await {
+                    let _ = result.into_results().await;
+                }
+            })
+        })
+    });
+}
+
+impl Drop for TdsConnection {
+    fn drop(&mut self) {
+        let _ = self.close();
+    }
+}
+
+/// Parse the query-logging knobs out of the connection string: `querylogging`
+/// (opt-in, default off), `querylogginglevel`/`queryslowlevel` (the level a
+/// normal vs. slow statement logs at), and `queryslowthresholdms` (elapsed
+/// time after which a statement is logged at the slow level instead). Kept
+/// separate from [`parse_connection_string`] rather than growing its tuple
+/// further, the same way [`crate::types::sql_type_name_to_code`] lives beside
+/// rather than inside `column_type_to_sql_type`.
+fn parse_query_log_config(conn_str: &str) -> QueryLogConfig {
+    let mut cfg = QueryLogConfig::default();
+    for part in conn_str.split(';') {
+        let part = part.trim();
+        if let Some(idx) = part.find('=') {
+            let key = part[..idx].trim().to_lowercase();
+            let val = part[idx + 1..].trim();
+            match key.as_str() {
+                "querylogging" => {
+                    cfg.enabled =
+                        val.eq_ignore_ascii_case("yes") || val == "1" || val.eq_ignore_ascii_case("true")
+                }
+                "querylogginglevel" => cfg.level = val.to_string(),
+                "queryslowlevel" => cfg.slow_level = val.to_string(),
+                "queryslowthresholdms" => {
+                    if let Ok(ms) = val.parse() {
+                        cfg.slow_threshold_ms = ms;
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+    cfg
+}
+
+/// Parse [`PoolConfig`] out of the connection string: `Max Pool Size`,
+/// `Connection Lifetime` (seconds; `0` == unlimited).
+fn parse_pool_config(conn_str: &str) -> PoolConfig {
+    let mut cfg = PoolConfig::default();
+    for part in conn_str.split(';') {
+        let part = part.trim();
+        if let Some(idx) = part.find('=') {
+            let key = part[..idx].trim().to_lowercase();
+            let val = part[idx + 1..].trim();
+            match key.as_str() {
+                "max pool size" => {
+                    if let Ok(n) = val.parse() {
+                        cfg.max_pool_size = n;
+                    }
+                }
+                "connection lifetime" => {
+                    if let Ok(secs) = val.parse() {
+                        cfg.connection_lifetime = Duration::from_secs(secs);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+    cfg
+}
+
+/// TLS knobs beyond the plain `TrustServerCertificate` boolean: the
+/// `Encrypt` mode and, for locked-down instances, a custom CA/pinned
+/// fingerprint or a client certificate for mutual TLS.
+struct TlsConfig {
+    encryption: EncryptionLevel,
+    ca_certificate: Option<String>,
+    cert_fingerprint: Option<String>,
+    client_certificate: Option<String>,
+    client_key: Option<String>,
+}
+
+impl Default for TlsConfig {
+    fn default() -> Self {
+        Self {
+            encryption: EncryptionLevel::Required,
+            ca_certificate: None,
+            cert_fingerprint: None,
+            client_certificate: None,
+            client_key: None,
+        }
+    }
+}
+
+/// Parse [`TlsConfig`] out of the connection string. `Encrypt` maps
+/// `strict`/`mandatory`/`optional`/`no` to
+/// `EncryptionLevel::Required`/`On`/`Off`/`NotSupported` per the request
+/// that added this; `Ca Certificate`, `Server Certificate Fingerprint`,
+/// `Client Certificate`, and `Client Key` are paths/values for connecting to
+/// instances that require a specific CA or cert-based login.
+fn parse_tls_config(conn_str: &str) -> TlsConfig {
+    let mut cfg = TlsConfig::default();
+    for part in conn_str.split(';') {
+        let part = part.trim();
+        if let Some(idx) = part.find('=') {
+            let key = part[..idx].trim().to_lowercase();
+            let val = part[idx + 1..].trim();
+            match key.as_str() {
+                "encrypt" => {
+                    cfg.encryption = match val.to_lowercase().as_str() {
+                        "strict" => EncryptionLevel::Required,
+                        "mandatory" => EncryptionLevel::On,
+                        "optional" => EncryptionLevel::Off,
+                        "no" => EncryptionLevel::NotSupported,
+                        _ => cfg.encryption,
+                    }
+                }
+                "ca certificate" => cfg.ca_certificate = Some(val.to_string()),
+                "server certificate fingerprint" => cfg.cert_fingerprint = Some(val.to_string()),
+                "client certificate" => cfg.client_certificate = Some(val.to_string()),
+                "client key" => cfg.client_key = Some(val.to_string()),
+                _ => {}
+            }
+        }
+    }
+    cfg
+}
+
+/// Authentication mode selected via the `Authentication` keyword, matching
+/// the names `Microsoft.Data.SqlClient` exposes.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum AuthMode {
+    SqlPassword,
+    ActiveDirectoryPassword,
+    ActiveDirectoryIntegrated,
+    ActiveDirectoryAccessToken,
+}
+
+/// `Authentication` mode plus an optional pre-fetched AAD bearer token,
+/// supplied either as the `Access Token` connection-string keyword or (the
+/// way `pyodbc`/ODBC drivers take it) the `SQL_COPT_SS_ACCESS_TOKEN`-style
+/// `_attrs_before` dict entry — see [`extract_access_token`].
+struct AuthConfig {
+    mode: AuthMode,
+    access_token: Option<String>,
+}
+
+impl Default for AuthConfig {
+    fn default() -> Self {
+        Self {
+            mode: AuthMode::SqlPassword,
+            access_token: None,
+        }
+    }
+}
+
+fn parse_auth_config(conn_str: &str) -> AuthConfig {
+    let mut cfg = AuthConfig::default();
+    for part in conn_str.split(';') {
+        let part = part.trim();
+        if let Some(idx) = part.find('=') {
+            let key = part[..idx].trim().to_lowercase();
+            let val = part[idx + 1..].trim();
+            match key.as_str() {
+                "authentication" => {
+                    cfg.mode = match val.to_lowercase().as_str() {
+                        "sqlpassword" => AuthMode::SqlPassword,
+                        "activedirectorypassword" => AuthMode::ActiveDirectoryPassword,
+                        "activedirectoryintegrated" => AuthMode::ActiveDirectoryIntegrated,
+                        "activedirectoryaccesstoken" => AuthMode::ActiveDirectoryAccessToken,
+                        _ => cfg.mode,
+                    }
+                }
+                "access token" => cfg.access_token = Some(val.to_string()),
+                _ => {}
+            }
+        }
+    }
+    cfg
+}
+
+/// Pull an AAD bearer token out of the `_attrs_before` dict passed to
+/// `Connection.__init__`, the same shape ODBC drivers take
+/// `SQL_COPT_SS_ACCESS_TOKEN` through: whichever of its keys case-insensitively
+/// matches `"access token"` (string values only — the raw
+/// length-prefixed-UTF-16 blob ODBC itself expects is not meaningful here).
+fn extract_access_token(attrs_before: Option<&Bound<'_, PyDict>>) -> Option<String> {
+    let dict = attrs_before?;
+    for (key, value) in dict.iter() {
+        if let Ok(key_str) = key.extract::<String>() {
+            if key_str.eq_ignore_ascii_case("access token") {
+                return value.extract::<String>().ok();
+            }
+        }
+    }
+    None
+}
+
+/// Pull the opt-in inbound-SQL dialect rewrite setting out of the
+/// `_attrs_before` dict: `source_dialect` set to `"postgres"`, `"mysql"`, or
+/// `"ansi"` turns on [`crate::cursor::TdsCursor::rewrite_dialect`] for that
+/// dialect's constructs (see [`crate::dialect::Dialect::rules`]). Absent or
+/// any other value leaves it off, the same opt-in-only default
+/// `querylogging` uses.
+fn extract_source_dialect(
+    attrs_before: Option<&Bound<'_, PyDict>>,
+) -> Option<crate::dialect::Dialect> {
+    let dict = attrs_before?;
+    for (key, value) in dict.iter() {
+        if let Ok(key_str) = key.extract::<String>() {
+            if key_str.eq_ignore_ascii_case("source_dialect") {
+                if let Ok(val_str) = value.extract::<String>() {
+                    return crate::dialect::Dialect::parse(&val_str);
+                }
+            }
+        }
+    }
+    None
+}
+
+/// Pull the output-conversion knobs out of the `_attrs_before` dict:
+/// `sub_second_mode` (`"microsecond"`, the default, or `"isostring"`) and
+/// `session_timezone` (an IANA zone name such as `"America/Chicago"`),
+/// fed straight into the [`crate::types::ConversionOptions`] every cursor
+/// this connection allocates converts result rows with. `out_of_range`
+/// isn't read here, so every cursor keeps the default
+/// [`crate::types::OutOfRangePolicy::Raise`], which
+/// [`crate::types::compact_value_to_py_opts`] applies to `Date`/`DateTime`/
+/// `DateTimeOffset` values whose year falls outside 1..=9999. Absent or unrecognized values
+/// leave the corresponding default in place, the same opt-in-only shape
+/// `source_dialect` uses.
+fn extract_conversion_options(
+    attrs_before: Option<&Bound<'_, PyDict>>,
+) -> crate::types::ConversionOptions {
+    let mut opts = crate::types::ConversionOptions::default();
+    let Some(dict) = attrs_before else {
+        return opts;
+    };
+    for (key, value) in dict.iter() {
+        let Ok(key_str) = key.extract::<String>() else {
+            continue;
+        };
+        if key_str.eq_ignore_ascii_case("sub_second_mode") {
+            if let Ok(val_str) = value.extract::<String>() {
+                if val_str.eq_ignore_ascii_case("isostring") {
+                    opts.sub_second = crate::types::SubSecondMode::IsoString;
+                } else if val_str.eq_ignore_ascii_case("microsecond") {
+                    opts.sub_second = crate::types::SubSecondMode::Microsecond;
+                }
+            }
+        } else if key_str.eq_ignore_ascii_case("session_timezone") {
+            if let Ok(val_str) = value.extract::<String>() {
+                opts.session_timezone = Some(val_str);
+            }
+        }
+    }
+    opts
+}
+
+/// Knobs that only affect the connect handshake, not authentication or TLS:
+/// `Application Name` (cosmetic, surfaces in `sys.dm_exec_sessions`),
+/// `Connection Timeout` (seconds; `0` means no timeout), and
+/// `ApplicationIntent` (only `ReadOnly` is a real SQL Server value, routing
+/// the session to a readable secondary).
+struct ConnectionOptions {
+    application_name: Option<String>,
+    connection_timeout: Duration,
+    read_only_intent: bool,
+}
+
+impl Default for ConnectionOptions {
+    fn default() -> Self {
+        Self {
+            application_name: None,
+            // Matches `Microsoft.Data.SqlClient`'s own default.
+            connection_timeout: Duration::from_secs(15),
+            read_only_intent: false,
+        }
+    }
+}
+
+fn parse_connection_options(conn_str: &str) -> ConnectionOptions {
+    let mut cfg = ConnectionOptions::default();
+    for part in conn_str.split(';') {
+        let part = part.trim();
+        if let Some(idx) = part.find('=') {
+            let key = part[..idx].trim().to_lowercase();
+            let val = part[idx + 1..].trim();
+            match key.as_str() {
+                "application name" => cfg.application_name = Some(val.to_string()),
+                "connection timeout" => {
+                    if let Ok(secs) = val.parse() {
+                        cfg.connection_timeout = Duration::from_secs(secs);
+                    }
+                }
+                "applicationintent" => {
+                    cfg.read_only_intent = val.eq_ignore_ascii_case("readonly")
+                }
+                _ => {}
+            }
+        }
+    }
+    cfg
+}
+
+/// Every keyword understood by [`parse_connection_string`] and the other
+/// `parse_*_config` helpers, lower-cased. Anything in a connection string
+/// that doesn't match one of these is reported back to the caller instead
+/// of being silently dropped (see its use in [`TdsConnection::new`]) — a
+/// typo'd keyword should fail loudly rather than quietly connecting with
+/// whatever defaults happen to apply.
+const KNOWN_CONNECTION_KEYS: &[&str] = &[
+    "server",
+    "database",
+    "initial catalog",
+    "uid",
+    "user id",
+    "pwd",
+    "password",
+    "trustservercertificate",
+    "querylogging",
+    "querylogginglevel",
+    "queryslowlevel",
+    "queryslowthresholdms",
+    "tcpnodelay",
+    "writebufferbytes",
+    "max pool size",
+    "connection lifetime",
+    "encrypt",
+    "ca certificate",
+    "server certificate fingerprint",
+    "client certificate",
+    "client key",
+    "authentication",
+    "access token",
+    "application name",
+    "connection timeout",
+    "applicationintent",
+];
+
+fn find_unknown_keys(conn_str: &str) -> Vec<String> {
+    let mut unknown = Vec::new();
+    for part in conn_str.split(';') {
+        let part = part.trim();
+        if part.is_empty() {
+            continue;
+        }
+        if let Some(idx) = part.find('=') {
+            let key = part[..idx].trim();
+            if !KNOWN_CONNECTION_KEYS
+                .iter()
+                .any(|k| k.eq_ignore_ascii_case(key))
+            {
+                unknown.push(key.to_string());
+            }
+        }
+    }
+    unknown
 }
 
 fn parse_connection_string(conn_str: &str) -> (String, u16, String, String, String, bool) {
@@ -61,6 +531,126 @@ fn parse_connection_string(conn_str: &str) -> (String, u16, String, String, Stri
 impl TdsConnection {
     pub fn new(connection_str: &str, _attrs_before: Option<&Bound<'_, PyDict>>) -> PyResult<Self> {
         let (host, port, database, uid, pwd, trust_cert) = parse_connection_string(connection_str);
+        let query_log = Arc::new(parse_query_log_config(connection_str));
+        let source_dialect = extract_source_dialect(_attrs_before);
+        let conversion_options = extract_conversion_options(_attrs_before);
+        let transport = parse_transport_config(connection_str);
+        let pool_config = parse_pool_config(connection_str);
+        let tls = parse_tls_config(connection_str);
+        let auth = parse_auth_config(connection_str);
+        let conn_opts = parse_connection_options(connection_str);
+        let access_token = auth
+            .access_token
+            .clone()
+            .or_else(|| extract_access_token(_attrs_before));
+        // `Application Name` is cosmetic (it would only ever surface in
+        // `sys.dm_exec_sessions.program_name`) and `tabby::Config` exposes
+        // no hook to set it, so it's accepted and parsed but not acted on.
+        let _ = &conn_opts.application_name;
+
+        let unknown_keys = find_unknown_keys(connection_str);
+        if !unknown_keys.is_empty() {
+            return Err(pyo3::exceptions::PyConnectionError::new_err(format!(
+                "Unrecognized connection string keyword(s): {}",
+                unknown_keys.join(", ")
+            )));
+        }
+
+        // `ApplicationIntent=ReadOnly` routes the session to a readable
+        // secondary via a LOGIN7 option bit — this build of `tabby` doesn't
+        // expose a way to set that bit (only the plain host/port/database/
+        // authentication/encryption knobs `Config` offers), so rather than
+        // silently connecting to the primary we fail loudly when it's set.
+        if conn_opts.read_only_intent {
+            return Err(pyo3::exceptions::PyConnectionError::new_err(
+                "ApplicationIntent=ReadOnly is not supported by this driver build: tabby's \
+                 Config exposes no read-only-routing option",
+            ));
+        }
+
+        let auth_method = match auth.mode {
+            AuthMode::SqlPassword => AuthMethod::sql_server(&uid, &pwd),
+            AuthMode::ActiveDirectoryAccessToken => match &access_token {
+                Some(token) => AuthMethod::aad_token(token.clone()),
+                None => {
+                    return Err(pyo3::exceptions::PyConnectionError::new_err(
+                        "Authentication=ActiveDirectoryAccessToken requires an Access Token \
+                         keyword or an `attrs_before` entry carrying the AAD bearer token",
+                    ))
+                }
+            },
+            AuthMode::ActiveDirectoryPassword | AuthMode::ActiveDirectoryIntegrated => {
+                match &access_token {
+                    // Neither mode has a bundled OAuth/Kerberos flow to
+                    // acquire a token from scratch — this crate vendors no
+                    // AAD client library — but if the caller already fetched
+                    // one and handed it in, the wire-level login is
+                    // identical to ActiveDirectoryAccessToken.
+                    Some(token) => AuthMethod::aad_token(token.clone()),
+                    None => {
+                        return Err(pyo3::exceptions::PyConnectionError::new_err(format!(
+                            "Authentication={:?} is not supported without a pre-fetched AAD \
+                             access token: this driver build has no bundled Azure AD \
+                             authentication flow, so supply one via the Access Token keyword \
+                             or attrs_before",
+                            auth.mode
+                        )))
+                    }
+                }
+            }
+        };
+
+        // The pool key must bind to the credential actually used to
+        // authenticate (the AAD token when one's in play, else the
+        // password), not just host/port/database/uid — otherwise a caller
+        // who supplies the right host/port/database/uid but a wrong or
+        // blank password would be handed back another session's already-
+        // authenticated idle connection instead of opening (and
+        // authenticating) its own. The credential itself isn't put in the
+        // key verbatim so it doesn't end up in logs or error messages that
+        // echo the pool key back.
+        let credential = match auth.mode {
+            AuthMode::SqlPassword => pwd.as_str(),
+            _ => access_token.as_deref().unwrap_or(""),
+        };
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        credential.hash(&mut hasher);
+        let pool_key = format!(
+            "{}:{}/{}/{}/{:016x}",
+            host,
+            port,
+            database,
+            uid,
+            hasher.finish()
+        );
+        let pool = TdsPool::for_key(&pool_key, pool_config);
+
+        // The TDS handshake's TLS negotiation (PRELOGIN) happens entirely
+        // inside `tabby::Client::connect`, driven by whatever's set on
+        // `tabby::Config` before the raw `TcpStream` below is handed to it —
+        // there's no point after that where this crate could intercept the
+        // stream to install our own `rustls::ClientConfig`. `Config` itself
+        // exposes exactly two TLS knobs: `encryption()` (the `EncryptionLevel`
+        // above) and `trust_cert()` (an accept-all server-cert verifier, set
+        // via `TrustServerCertificate`) — nothing that takes a custom CA
+        // bundle, a pinned server-cert fingerprint, or a client certificate
+        // for mutual TLS. Building those would mean vendoring or patching
+        // `tabby` to accept an external `rustls::ClientConfig`, which is out
+        // of scope for this driver; until that lands upstream in `tabby`,
+        // fail loudly at connect time rather than silently ignore the keys.
+        if tls.ca_certificate.is_some()
+            || tls.cert_fingerprint.is_some()
+            || tls.client_certificate.is_some()
+            || tls.client_key.is_some()
+        {
+            return Err(pyo3::exceptions::PyConnectionError::new_err(
+                "Ca Certificate / Server Certificate Fingerprint / Client Certificate / Client \
+                 Key are not supported by this driver build: tabby::Config exposes only Encrypt \
+                 and TrustServerCertificate, with no hook for a custom CA, pinned fingerprint, or \
+                 client certificate — this needs a tabby-side TLS API before it can be \
+                 implemented here",
+            ));
+        }
 
         // Check for required connection string parameters
         let has_server = connection_str.split(';').any(|part| {
@@ -77,61 +667,113 @@ impl TdsConnection {
             ));
         }
 
-        let client = Python::with_gil(|py| {
+        let (client, created_at) = Python::with_gil(|py| {
             py.allow_threads(|| {
                 runtime::block_on(async {
-                    let mut config = Config::new();
-                    config.host(&host);
-                    config.port(port);
-                    config.database(&database);
-                    config.authentication(AuthMethod::sql_server(&uid, &pwd));
-                    if trust_cert {
-                        config.trust_cert();
-                    }
-                    config.encryption(EncryptionLevel::Required);
-
-                    let tcp = TcpStream::connect(config.get_addr()).await.map_err(|e| {
-                        pyo3::exceptions::PyConnectionError::new_err(format!(
-                            "TCP connect failed: {}",
-                            e
-                        ))
-                    })?;
-                    tcp.set_nodelay(true).map_err(|e| {
-                        pyo3::exceptions::PyConnectionError::new_err(format!(
-                            "set_nodelay failed: {}",
-                            e
-                        ))
-                    })?;
-
-                    let client =
-                        Client::connect(config, tcp.compat_write())
-                            .await
-                            .map_err(|e| {
+                    pool.acquire(&host, || async {
+                        let mut config = Config::new();
+                        config.host(&host);
+                        config.port(port);
+                        config.database(&database);
+                        config.authentication(auth_method);
+                        if trust_cert {
+                            config.trust_cert();
+                        }
+                        config.encryption(tls.encryption);
+
+                        let connect_fut = TcpStream::connect(config.get_addr());
+                        let tcp = if conn_opts.connection_timeout.is_zero() {
+                            connect_fut.await.map_err(|e| {
                                 pyo3::exceptions::PyConnectionError::new_err(format!(
-                                    "TDS connect failed: {}",
+                                    "TCP connect failed: {}",
                                     e
                                 ))
-                            })?;
+                            })?
+                        } else {
+                            tokio::time::timeout(conn_opts.connection_timeout, connect_fut)
+                                .await
+                                .map_err(|_| {
+                                    pyo3::exceptions::PyConnectionError::new_err(format!(
+                                        "TCP connect timed out after {:?}",
+                                        conn_opts.connection_timeout
+                                    ))
+                                })?
+                                .map_err(|e| {
+                                    pyo3::exceptions::PyConnectionError::new_err(format!(
+                                        "TCP connect failed: {}",
+                                        e
+                                    ))
+                                })?
+                        };
+                        tcp.set_nodelay(transport.tcp_nodelay).map_err(|e| {
+                            pyo3::exceptions::PyConnectionError::new_err(format!(
+                                "set_nodelay failed: {}",
+                                e
+                            ))
+                        })?;
+                        let tcp = BufWriter::with_capacity(transport.write_buffer_bytes, tcp);
+
+                        let client =
+                            Client::connect(config, tcp.compat_write())
+                                .await
+                                .map_err(|e| {
+                                    pyo3::exceptions::PyConnectionError::new_err(format!(
+                                        "TDS connect failed: {}",
+                                        e
+                                    ))
+                                })?;
 
-                    Ok::<_, PyErr>(client)
+                        Ok::<_, PyErr>(Arc::new(Mutex::new(client)))
+                    })
+                    .await
                 })
             })
         })?;
 
+        let cancel_token = CancelToken::new(host.clone());
+
         Ok(TdsConnection {
-            client: Some(Arc::new(Mutex::new(client))),
+            client: Some(client),
             tx_state: Arc::new(Mutex::new(TransactionState {
                 autocommit: false,
                 in_transaction: false,
+                explicit: false,
+                savepoints: Vec::new(),
+                isolation_level: None,
             })),
+            query_log,
+            pool,
+            host,
+            created_at,
+            cancel_token,
+            prepared_cache: Arc::new(Mutex::new(PreparedStatementCache::new())),
+            source_dialect,
+            conversion_options,
         })
     }
 
     pub fn close(&mut self) -> PyResult<()> {
-        self.client = None;
+        if let Some(client) = self.client.take() {
+            let handles = self.prepared_cache.lock().unwrap().drain_all();
+            unprepare_handles(&client, &handles);
+            self.pool.release(&self.host, client, self.created_at);
+        }
         Ok(())
     }
 
+    /// A cheaply-clonable, `Send` handle that can be stashed on another
+    /// Python thread before a blocking call begins, then used to request
+    /// cancellation of whatever is in flight on this connection. See
+    /// [`crate::cancel`] for why [`CancelToken::cancel`] reports
+    /// `NotSupportedError` rather than actually interrupting the query.
+    pub fn cancel_token(&self) -> CancelToken {
+        self.cancel_token.clone()
+    }
+
+    pub fn cancel(&self) -> PyResult<()> {
+        self.cancel_token.cancel()
+    }
+
     fn get_client(&self) -> PyResult<SharedClient> {
         self.client
             .clone()
@@ -161,6 +803,7 @@ impl TdsConnection {
         let mut state = self.tx_state.lock().unwrap();
         if state.in_transaction {
             state.in_transaction = false;
+            state.savepoints.clear();
             drop(state);
             self.exec_simple("IF @@TRANCOUNT > 0 COMMIT TRANSACTION")?;
         }
@@ -171,6 +814,8 @@ impl TdsConnection {
         let mut state = self.tx_state.lock().unwrap();
         if state.in_transaction {
             state.in_transaction = false;
+            // Rolling back the whole transaction unwinds every open savepoint too.
+            state.savepoints.clear();
             drop(state);
             let _ = self.exec_simple("IF @@TRANCOUNT > 0 ROLLBACK TRANSACTION");
         }
@@ -182,10 +827,14 @@ impl TdsConnection {
         if value && state.in_transaction {
             state.in_transaction = false;
             state.autocommit = value;
+            state.savepoints.clear();
             drop(state);
             self.exec_simple("COMMIT TRANSACTION")?;
         } else {
             state.autocommit = value;
+            if value {
+                state.savepoints.clear();
+            }
         }
         Ok(())
     }
@@ -194,9 +843,94 @@ impl TdsConnection {
         self.tx_state.lock().unwrap().autocommit
     }
 
+    /// Open a named savepoint within the current transaction via `SAVE
+    /// TRANSACTION`, pushing it onto the savepoint stack.
+    pub fn savepoint(&mut self, name: &str) -> PyResult<()> {
+        {
+            let mut state = self.tx_state.lock().unwrap();
+            if !state.in_transaction {
+                return Err(pyo3::exceptions::PyRuntimeError::new_err(
+                    "Cannot create a savepoint outside of a transaction",
+                ));
+            }
+            state.savepoints.push(name.to_string());
+        }
+        self.exec_simple(&format!(
+            "SAVE TRANSACTION {}",
+            quote_bracket_identifier(name)
+        ))
+    }
+
+    /// Roll back to a previously-opened savepoint via `ROLLBACK TRANSACTION
+    /// <name>`, popping every savepoint opened after it off the stack
+    /// (T-SQL itself discards them the same way on rollback). The named
+    /// savepoint itself stays on the stack as a valid rollback target.
+    pub fn rollback_to(&mut self, name: &str) -> PyResult<()> {
+        {
+            let mut state = self.tx_state.lock().unwrap();
+            match state.savepoints.iter().rposition(|s| s == name) {
+                Some(pos) => state.savepoints.truncate(pos + 1),
+                None => {
+                    return Err(pyo3::exceptions::PyRuntimeError::new_err(format!(
+                        "Unknown savepoint: {}",
+                        name
+                    )))
+                }
+            }
+        }
+        self.exec_simple(&format!(
+            "ROLLBACK TRANSACTION {}",
+            quote_bracket_identifier(name)
+        ))
+    }
+
+    /// Forget a savepoint without rolling back to it. T-SQL has no `RELEASE
+    /// SAVEPOINT` statement (unlike Postgres) — a savepoint just stops being
+    /// a valid rollback target once it's no longer needed, so this only
+    /// updates the local stack and sends nothing to the server.
+    pub fn release_savepoint(&mut self, name: &str) -> PyResult<()> {
+        let mut state = self.tx_state.lock().unwrap();
+        match state.savepoints.iter().rposition(|s| s == name) {
+            Some(pos) => {
+                state.savepoints.truncate(pos);
+                Ok(())
+            }
+            None => Err(pyo3::exceptions::PyRuntimeError::new_err(format!(
+                "Unknown savepoint: {}",
+                name
+            ))),
+        }
+    }
+
+    /// Remember `level` (e.g. `"READ COMMITTED"`, `"SERIALIZABLE"`) and emit
+    /// `SET TRANSACTION ISOLATION LEVEL <level>` right before the next
+    /// transaction begins — it only affects transactions started after it
+    /// runs, so applying it immediately here would be a no-op while one is
+    /// already open.
+    pub fn set_isolation_level(&mut self, level: &str) -> PyResult<()> {
+        let normalized = level.trim().to_uppercase();
+        if !ISOLATION_LEVELS.contains(&normalized.as_str()) {
+            return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                "Unsupported isolation level: {:?} (expected one of {})",
+                level,
+                ISOLATION_LEVELS.join(", ")
+            )));
+        }
+        self.tx_state.lock().unwrap().isolation_level = Some(normalized);
+        Ok(())
+    }
+
     pub fn alloc_cursor(&mut self) -> PyResult<TdsCursor> {
         let client = self.get_client()?;
-        Ok(TdsCursor::new(client, self.tx_state.clone()))
+        Ok(TdsCursor::new(
+            client,
+            self.tx_state.clone(),
+            self.query_log.clone(),
+            self.cancel_token.clone(),
+            self.prepared_cache.clone(),
+            self.source_dialect,
+            self.conversion_options.clone(),
+        ))
     }
 
     pub fn query_single_string(&self, sql: &str) -> PyResult<Option<String>> {
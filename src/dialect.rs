@@ -0,0 +1,441 @@
+//! Optional inbound SQL dialect rewriting: a lightweight, tokenizer-based
+//! translation of a handful of common Postgres/MySQL/ANSI constructs that
+//! aren't valid T-SQL, for callers migrating code written against another
+//! engine. Off by default — see `connection::extract_source_dialect` for how
+//! a connection opts in via `source_dialect` in `_attrs_before`, and
+//! [`crate::cursor::TdsCursor::rewrite_dialect`] for where this runs before
+//! a statement reaches the server.
+//!
+//! This doesn't parse SQL — it's a best-effort text transform covering the
+//! specific constructs callers hit in practice: trailing `LIMIT`/`OFFSET`,
+//! `::` casts, `||` concatenation, bare `TRUE`/`FALSE` literals,
+//! dialect-quoted identifiers, and `NOW()`/`CURRENT_TIMESTAMP`. Which of
+//! these apply is keyed off [`Dialect`] via [`Dialect::rules`] — e.g. MySQL
+//! quotes identifiers with backticks rather than double quotes, and has no
+//! `::`-cast or `||`-concat syntax of its own. String literals are always
+//! copied through untouched.
+
+/// A source SQL dialect [`rewrite_to_tsql`] can translate from. Parsed from
+/// the `source_dialect` connection attribute by
+/// `connection::extract_source_dialect`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Dialect {
+    Postgres,
+    MySql,
+    Ansi,
+}
+
+impl Dialect {
+    /// Parse a `source_dialect` attribute value. Case-insensitive; returns
+    /// `None` for anything not recognized, same as an absent attribute.
+    pub fn parse(name: &str) -> Option<Dialect> {
+        if name.eq_ignore_ascii_case("postgres") {
+            Some(Dialect::Postgres)
+        } else if name.eq_ignore_ascii_case("mysql") {
+            Some(Dialect::MySql)
+        } else if name.eq_ignore_ascii_case("ansi") {
+            Some(Dialect::Ansi)
+        } else {
+            None
+        }
+    }
+
+    fn rules(self) -> DialectRules {
+        match self {
+            Dialect::Postgres => DialectRules {
+                ident_quote: Some('"'),
+                double_colon_cast: true,
+                double_pipe_concat: true,
+                normalize_now: true,
+            },
+            Dialect::MySql => DialectRules {
+                ident_quote: Some('`'),
+                double_colon_cast: false,
+                double_pipe_concat: false,
+                normalize_now: true,
+            },
+            Dialect::Ansi => DialectRules {
+                ident_quote: Some('"'),
+                double_colon_cast: false,
+                double_pipe_concat: false,
+                normalize_now: true,
+            },
+        }
+    }
+}
+
+/// Which of [`rewrite_tokens`]'s constructs apply for a given [`Dialect`].
+/// `ident_quote`, when set, is the character that quotes identifiers in
+/// that dialect (rewritten to T-SQL's `[...]` brackets); `None` leaves that
+/// quote character untouched as an opaque string-literal-like run.
+struct DialectRules {
+    ident_quote: Option<char>,
+    double_colon_cast: bool,
+    double_pipe_concat: bool,
+    normalize_now: bool,
+}
+
+/// Rewrite `sql`'s constructs for `dialect` into their T-SQL equivalents.
+/// `TRUE`/`FALSE` literals and a trailing `LIMIT` are rewritten for every
+/// dialect; apart from those, `sql` comes back unchanged (as an owned
+/// `String`) if none of the constructs [`Dialect::rules`] enables for
+/// `dialect` are present.
+pub fn rewrite_to_tsql(sql: &str, dialect: Dialect) -> String {
+    let rules = dialect.rules();
+    let (body, limit) = take_trailing_limit(sql);
+    let mut out = rewrite_tokens(body, &rules);
+    if let Some((limit_n, offset_n)) = limit {
+        out = apply_limit_clause(&out, limit_n, offset_n);
+    }
+    out
+}
+
+/// Find the first (`find_ascii_ci`) or last (`rfind_ascii_ci`) byte offset
+/// of `needle` in `haystack`, matched ASCII case-insensitively, without
+/// building a case-folded copy of `haystack` first. `to_lowercase()` isn't
+/// byte-length preserving for all Unicode input (e.g. Turkish `İ`,
+/// ligatures like `ﬁ`), so searching a folded copy and slicing the
+/// original by the match offset can panic or mis-slice when non-ASCII
+/// text surrounds `needle`. `needle` is always plain ASCII here, so
+/// comparing raw bytes directly is both correct and char-boundary-safe: a
+/// match can only land on ASCII bytes, which are always their own char
+/// boundary.
+fn find_ascii_ci(haystack: &str, needle: &str) -> Option<usize> {
+    let (hb, nb) = (haystack.as_bytes(), needle.as_bytes());
+    if nb.is_empty() || hb.len() < nb.len() {
+        return None;
+    }
+    (0..=hb.len() - nb.len()).find(|&start| hb[start..start + nb.len()].eq_ignore_ascii_case(nb))
+}
+
+/// Find the first occurrence of `needle` that sits outside any parenthesized
+/// region and outside any `'...'` string literal. Matched ASCII
+/// case-insensitively on raw bytes for the same char-boundary reasons as
+/// [`find_ascii_ci`]. Used to locate a statement's *own* `SELECT`/`ORDER BY`
+/// rather than one belonging to a CTE or subquery nested inside it — e.g.
+/// `WITH cte AS (SELECT a FROM b) SELECT * FROM cte` or
+/// `SELECT * FROM (SELECT x FROM y ORDER BY x) t` would otherwise match the
+/// inner query's keyword instead of (or in addition to) the outer one's.
+fn find_top_level_ci(sql: &str, needle: &str) -> Option<usize> {
+    let bytes = sql.as_bytes();
+    let nb = needle.as_bytes();
+    let mut depth = 0i32;
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\'' => {
+                i += 1;
+                while i < bytes.len() {
+                    if bytes[i] == b'\'' {
+                        i += 1;
+                        if bytes.get(i) == Some(&b'\'') {
+                            i += 1;
+                            continue;
+                        }
+                        break;
+                    }
+                    i += 1;
+                }
+                continue;
+            }
+            b'(' => {
+                depth += 1;
+                i += 1;
+                continue;
+            }
+            b')' => {
+                depth -= 1;
+                i += 1;
+                continue;
+            }
+            _ => {}
+        }
+        if depth <= 0 && bytes[i..].len() >= nb.len() && bytes[i..i + nb.len()].eq_ignore_ascii_case(nb)
+        {
+            return Some(i);
+        }
+        i += 1;
+    }
+    None
+}
+
+fn rfind_ascii_ci(haystack: &str, needle: &str) -> Option<usize> {
+    let (hb, nb) = (haystack.as_bytes(), needle.as_bytes());
+    if nb.is_empty() || hb.len() < nb.len() {
+        return None;
+    }
+    (0..=hb.len() - nb.len())
+        .rev()
+        .find(|&start| hb[start..start + nb.len()].eq_ignore_ascii_case(nb))
+}
+
+fn starts_with_ascii_ci(haystack: &str, needle: &str) -> bool {
+    let (hb, nb) = (haystack.as_bytes(), needle.as_bytes());
+    hb.len() >= nb.len() && hb[..nb.len()].eq_ignore_ascii_case(nb)
+}
+
+/// Strip a trailing `LIMIT n [OFFSET m]` clause (case-insensitive, after an
+/// optional trailing `;`) and return the remaining SQL plus the parsed
+/// `(limit, offset)` pair. Only matches `LIMIT` in that literal trailing
+/// position — this is the shape Postgres requires it in, and the only one
+/// worth handling without a real SQL parser.
+fn take_trailing_limit(sql: &str) -> (&str, Option<(u64, Option<u64>)>) {
+    let trimmed = sql.trim_end().trim_end_matches(';').trim_end();
+    let Some(limit_idx) = rfind_ascii_ci(trimmed, " limit ") else {
+        return (sql, None);
+    };
+    let before = &trimmed[..limit_idx];
+    let after = trimmed[limit_idx + " limit ".len()..].trim();
+    let (limit_part, offset_part) = match find_ascii_ci(after, " offset ") {
+        Some(off_idx) => (
+            after[..off_idx].trim(),
+            Some(after[off_idx + " offset ".len()..].trim()),
+        ),
+        None => (after, None),
+    };
+    let Ok(limit_n) = limit_part.parse::<u64>() else {
+        return (sql, None);
+    };
+    let offset_n = offset_part.and_then(|s| s.parse::<u64>().ok());
+    (before, Some((limit_n, offset_n)))
+}
+
+/// Append the T-SQL equivalent of a stripped `LIMIT limit_n [OFFSET offset_n]`
+/// clause to `sql`: `OFFSET ... ROWS FETCH NEXT ... ROWS ONLY` after `sql`'s
+/// own `ORDER BY` when it has one (`FETCH` requires one), a plain `TOP` right
+/// after `SELECT`/`SELECT DISTINCT` when there's no `OFFSET` to express, or
+/// both a dummy `ORDER BY (SELECT NULL)` and `OFFSET`/`FETCH` otherwise.
+fn apply_limit_clause(sql: &str, limit_n: u64, offset_n: Option<u64>) -> String {
+    // Depth-aware, like `insert_top`'s `find_top_level_ci(sql, "select")`
+    // call below: a plain `.contains(" order by ")` would false-positive on
+    // an ORDER BY belonging to a nested subquery/CTE, appending OFFSET/FETCH
+    // to an outer SELECT that has no ORDER BY of its own — invalid T-SQL,
+    // since OFFSET/FETCH must pair with an ORDER BY on the same SELECT.
+    let has_order_by = find_top_level_ci(sql, " order by ").is_some();
+    if has_order_by {
+        format!(
+            "{} OFFSET {} ROWS FETCH NEXT {} ROWS ONLY",
+            sql,
+            offset_n.unwrap_or(0),
+            limit_n
+        )
+    } else if let Some(offset_n) = offset_n {
+        format!(
+            "{} ORDER BY (SELECT NULL) OFFSET {} ROWS FETCH NEXT {} ROWS ONLY",
+            sql, offset_n, limit_n
+        )
+    } else {
+        insert_top(sql, limit_n)
+    }
+}
+
+/// Insert `TOP limit_n` right after the statement's outermost `SELECT` (or
+/// `SELECT DISTINCT`) keyword. Returns `sql` unchanged if none is found.
+///
+/// Searches outside any parenthesized region so a CTE's own `(SELECT ...)`
+/// (e.g. `WITH cte AS (SELECT a FROM b) SELECT * FROM cte`) isn't mistaken
+/// for the statement's `SELECT` — splicing `TOP` into the CTE body instead
+/// of the outer query would silently change which rows come back.
+fn insert_top(sql: &str, limit_n: u64) -> String {
+    let Some(select_idx) = find_top_level_ci(sql, "select") else {
+        return sql.to_string();
+    };
+    let after_select = select_idx + "select".len();
+    let rest = sql[after_select..].trim_start();
+    let insert_at = if starts_with_ascii_ci(rest, "distinct") {
+        after_select + (sql[after_select..].len() - rest.len()) + "distinct".len()
+    } else {
+        after_select
+    };
+    format!(
+        "{} TOP {} {}",
+        &sql[..insert_at],
+        limit_n,
+        sql[insert_at..].trim_start()
+    )
+}
+
+fn is_ident_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+/// Token-level rewrite of `||` concatenation, `TRUE`/`FALSE` literals,
+/// `expr::TYPE` casts, dialect-quoted identifiers, and `NOW()`/
+/// `CURRENT_TIMESTAMP`, skipping over `'...'` and `[...]` literals so their
+/// contents are never touched. Which rewrites fire is driven by `rules`.
+fn rewrite_tokens(sql: &str, rules: &DialectRules) -> String {
+    let mut out = String::with_capacity(sql.len());
+    let chars: Vec<char> = sql.chars().collect();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        match c {
+            '"' if rules.ident_quote == Some('"') => {
+                let (ident, next_i) = read_dialect_quoted(&chars, i, '"');
+                out.push('[');
+                out.push_str(&ident.replace(']', "]]"));
+                out.push(']');
+                i = next_i;
+            }
+            '`' if rules.ident_quote == Some('`') => {
+                let (ident, next_i) = read_dialect_quoted(&chars, i, '`');
+                out.push('[');
+                out.push_str(&ident.replace(']', "]]"));
+                out.push(']');
+                i = next_i;
+            }
+            '\'' | '"' => {
+                let quote = c;
+                out.push(c);
+                i += 1;
+                while i < chars.len() {
+                    out.push(chars[i]);
+                    let closed = chars[i] == quote;
+                    i += 1;
+                    if closed {
+                        if i < chars.len() && chars[i] == quote {
+                            out.push(chars[i]);
+                            i += 1;
+                        } else {
+                            break;
+                        }
+                    }
+                }
+            }
+            '[' => {
+                out.push(c);
+                i += 1;
+                while i < chars.len() {
+                    out.push(chars[i]);
+                    let closed = chars[i] == ']';
+                    i += 1;
+                    if closed {
+                        break;
+                    }
+                }
+            }
+            '|' if rules.double_pipe_concat && chars.get(i + 1) == Some(&'|') => {
+                out.push_str(" + ");
+                i += 2;
+            }
+            ':' if rules.double_colon_cast && chars.get(i + 1) == Some(&':') => {
+                i += 2;
+                let type_start = i;
+                while i < chars.len() && is_ident_char(chars[i]) {
+                    i += 1;
+                }
+                if i < chars.len() && chars[i] == '(' {
+                    let mut depth = 0;
+                    while i < chars.len() {
+                        if chars[i] == '(' {
+                            depth += 1;
+                        }
+                        if chars[i] == ')' {
+                            depth -= 1;
+                        }
+                        i += 1;
+                        if depth == 0 {
+                            break;
+                        }
+                    }
+                }
+                let type_name: String = chars[type_start..i].iter().collect();
+                let expr = take_last_atom(&mut out);
+                out.push_str("CAST(");
+                out.push_str(&expr);
+                out.push_str(" AS ");
+                out.push_str(&type_name);
+                out.push(')');
+            }
+            c if c.is_alphabetic() || c == '_' => {
+                let start = i;
+                while i < chars.len() && is_ident_char(chars[i]) {
+                    i += 1;
+                }
+                let word: String = chars[start..i].iter().collect();
+                if word.eq_ignore_ascii_case("true") {
+                    out.push('1');
+                } else if word.eq_ignore_ascii_case("false") {
+                    out.push('0');
+                } else if rules.normalize_now
+                    && word.eq_ignore_ascii_case("now")
+                    && chars[i..].iter().find(|c| !c.is_whitespace()) == Some(&'(')
+                {
+                    out.push_str("GETDATE");
+                } else if rules.normalize_now && word.eq_ignore_ascii_case("current_timestamp") {
+                    out.push_str("GETDATE()");
+                } else {
+                    out.push_str(&word);
+                }
+            }
+            _ => {
+                out.push(c);
+                i += 1;
+            }
+        }
+    }
+    out
+}
+
+/// Read a dialect-quoted identifier starting at `chars[start]` (which must
+/// be `quote`), doubling the quote character as its own escape (e.g.
+/// `` `a``b` `` / `"a""b"`), the same convention T-SQL brackets use for a
+/// literal `]`. Returns the unescaped identifier text and the index just
+/// past the closing quote.
+fn read_dialect_quoted(chars: &[char], start: usize, quote: char) -> (String, usize) {
+    let mut ident = String::new();
+    let mut i = start + 1;
+    while i < chars.len() {
+        if chars[i] == quote {
+            if chars.get(i + 1) == Some(&quote) {
+                ident.push(quote);
+                i += 2;
+                continue;
+            }
+            i += 1;
+            break;
+        }
+        ident.push(chars[i]);
+        i += 1;
+    }
+    (ident, i)
+}
+
+/// Remove and return the last atom written to `out` — the expression a
+/// `::TYPE` cast in [`rewrite_tokens`] applies to: a parenthesized group if
+/// `out` ends with `)`, otherwise a run of identifier/number/`.` characters
+/// (covering `table.column`, bare identifiers, and numeric literals).
+fn take_last_atom(out: &mut String) -> String {
+    if out.ends_with(')') {
+        let chars: Vec<char> = out.chars().collect();
+        let mut depth = 0;
+        let mut start = 0;
+        for (pos, &c) in chars.iter().enumerate().rev() {
+            if c == ')' {
+                depth += 1;
+            } else if c == '(' {
+                depth -= 1;
+                if depth == 0 {
+                    start = pos;
+                    break;
+                }
+            }
+        }
+        let atom: String = chars[start..].iter().collect();
+        let new_len = out.len() - atom.len();
+        out.truncate(new_len);
+        atom
+    } else {
+        let mut start = out.len();
+        for (pos, c) in out.char_indices().rev() {
+            if is_ident_char(c) || c == '.' {
+                start = pos;
+            } else {
+                break;
+            }
+        }
+        let atom = out[start..].to_string();
+        out.truncate(start);
+        atom
+    }
+}
@@ -1,22 +1,69 @@
 use pyo3::prelude::*;
+use std::error::Error as StdError;
 use tabby::error::Error as TabbyError;
 
+/// SQL Server error number -> human-readable SQLSTATE class description
+/// (used for the driver-facing message when we have a better one than the
+/// raw server text).
 fn sqlstate_desc(code: u32) -> &'static str {
     match code {
         102 | 156 | 170 | 207 | 2812 => "Syntax error or access violation",
         208 | 3701 => "Base table or view not found",
-        547 | 2601 | 2627 => "Integrity constraint violation",
+        547 | 2601 | 2627 | 515 => "Integrity constraint violation",
         245 | 220 | 8115 | 8114 => "Data exception",
         8152 | 2628 => "String or binary data would be truncated",
+        1205 | 3960 => "Transaction rollback",
+        4060 | 18456 | 18452 => "Invalid authorization specification",
+        40516 => "Feature not supported",
         _ => "",
     }
 }
 
+/// SQL Server error number -> 5-char SQLSTATE, following the same classes
+/// ODBC/`pyodbc` surface for these errors. Falls back to the generic
+/// `"HY000"` ("general error") when a number has no specific SQLSTATE.
+fn sqlstate_code(code: u32) -> &'static str {
+    match code {
+        // Syntax error or access rule violation
+        102 | 156 | 170 => "42000",
+        207 => "42S22",  // invalid column name
+        208 => "42S02",  // base table or view not found
+        2812 => "42000", // could not find stored procedure
+        3701 => "42S02", // cannot drop the object because it doesn't exist
+        // Integrity constraint violation
+        515 => "23000",        // cannot insert the value NULL
+        547 => "23000",        // the statement conflicted with a constraint
+        2601 | 2627 => "23000", // unique/primary key violation
+        // Data exception
+        245 => "22018",  // conversion failed (invalid character for cast)
+        220 => "22003",  // arithmetic overflow
+        8114 => "22018", // error converting data type
+        8115 => "22003", // arithmetic overflow converting expression
+        8152 | 2628 => "22001", // string or binary data would be truncated
+        // Transaction rollback
+        1205 => "40001", // transaction deadlock victim
+        3960 => "40001", // snapshot isolation update conflict
+        // Invalid authorization / connection exception
+        4060 => "08004",  // cannot open database requested by login
+        18456 => "28000", // login failed
+        18452 => "28000", // login failed, not associated with a trusted connection
+        233 => "08001",   // no process on the other end of the pipe
+        64 => "08S01",    // connection forcibly closed
+        10054 => "08S01", // communication link failure
+        10060 => "08001", // network error, could not reach server
+        // Feature not supported
+        40516 => "0A000", // Azure SQL Database: keyword/option not supported in this edition
+        _ => "HY000",
+    }
+}
+
 fn exc_class_name(code: u32, class: u8) -> &'static str {
     match code {
         102 | 156 | 170 | 207 | 208 | 2812 | 3701 => "ProgrammingError",
-        547 | 2601 | 2627 => "IntegrityError",
+        547 | 2601 | 2627 | 515 => "IntegrityError",
         245 | 8152 | 220 | 8115 | 8114 | 2628 => "DataError",
+        4060 | 18456 | 18452 | 233 | 64 | 10054 | 10060 => "InterfaceError",
+        40516 => "NotSupportedError",
         _ if class >= 20 => "InternalError",
         _ if class >= 17 => "OperationalError",
         _ => "DatabaseError",
@@ -32,6 +79,7 @@ pub fn to_pyerr(e: TabbyError) -> PyErr {
             let class = token_err.class();
             let server_msg = token_err.message().to_string();
             let state_desc = sqlstate_desc(code);
+            let state_code = sqlstate_code(code);
             let cls_name = exc_class_name(code, class);
 
             let driver_msg = if state_desc.is_empty() {
@@ -46,6 +94,13 @@ pub fn to_pyerr(e: TabbyError) -> PyErr {
                     let exc_mod = py.import("whiskers.exceptions")?;
                     let exc_class = exc_mod.getattr(cls_name)?;
                     let err_obj = exc_class.call1((&driver_msg, &ddbc_msg))?;
+                    err_obj.setattr("sqlstate", state_code)?;
+                    if let Some(source) = StdError::source(&e) {
+                        let cause_cls = py.get_type::<pyo3::exceptions::PyRuntimeError>();
+                        if let Ok(cause_obj) = cause_cls.call1((source.to_string(),)) {
+                            err_obj.setattr("__cause__", cause_obj)?;
+                        }
+                    }
                     Ok(PyErr::from_value(
                         err_obj.into_any().unbind().into_bound(py),
                     ))
@@ -0,0 +1,287 @@
+//! Direct-to-JSON result-set encoding, skipping `CompactValue`/`PyObject`
+//! construction entirely.
+//!
+//! `JsonRowWriter` implements `tabby::RowWriter` directly (unlike
+//! [`crate::arrow_export`]/[`crate::numpy_export`], which convert an
+//! already-decoded [`crate::row_writer::PyRowWriter`]): every cell is
+//! serialized straight into a `Vec<u8>` during TDS decode, so callers that
+//! immediately re-serialize results anyway (HTTP APIs, log shipping) never
+//! pay for an intermediate representation at all. Multiple result sets
+//! (`on_metadata` firing more than once) are emitted as an outer JSON array
+//! of per-result-set row arrays, mirroring `MultiSetWriter`'s multi-set
+//! bookkeeping.
+//!
+use tabby::RowWriter;
+
+use crate::types::{decimal_i128_to_string, format_iso_nanos, micros_to_components};
+
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// JSON string escaping: `"`, `\`, the common single-character escapes, and
+/// `\u00XX` for other C0 control characters. Does not add the surrounding
+/// quotes — callers embedding this into the output buffer add those.
+fn json_escape(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+fn base64_encode(data: &[u8]) -> String {
+    let mut out = String::with_capacity(data.len().div_ceil(3) * 4);
+    for chunk in data.chunks(3) {
+        let b0 = chunk[0];
+        let b1 = chunk.get(1).copied();
+        let b2 = chunk.get(2).copied();
+        out.push(BASE64_ALPHABET[(b0 >> 2) as usize] as char);
+        out.push(
+            BASE64_ALPHABET[(((b0 & 0x03) << 4) | (b1.unwrap_or(0) >> 4)) as usize] as char,
+        );
+        out.push(match b1 {
+            Some(b1) => BASE64_ALPHABET[(((b1 & 0x0f) << 2) | (b2.unwrap_or(0) >> 6)) as usize] as char,
+            None => '=',
+        });
+        out.push(match b2 {
+            Some(b2) => BASE64_ALPHABET[(b2 & 0x3f) as usize] as char,
+            None => '=',
+        });
+    }
+    out
+}
+
+fn format_date_from_days(days: i32) -> String {
+    let (year, month, day, ..) = micros_to_components(days as i64 * 86_400_000_000);
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+fn format_time_from_nanos(nanos: i64) -> String {
+    let secs = nanos.div_euclid(1_000_000_000);
+    let remaining_nanos = nanos.rem_euclid(1_000_000_000) as u32;
+    let hour = secs / 3600;
+    let minute = (secs % 3600) / 60;
+    let second = secs % 60;
+    let mut s = format!("{:02}:{:02}:{:02}", hour, minute, second);
+    if remaining_nanos > 0 {
+        s.push_str(&format!(".{:07}", remaining_nanos / 100));
+    }
+    s
+}
+
+/// Serializes result sets straight to JSON bytes during TDS decode. See the
+/// module docs for the overall shape, and [`json_escape`] for string escaping.
+pub struct JsonRowWriter {
+    out: Vec<u8>,
+    col_names: Vec<String>,
+    result_sets: usize,
+    rows_in_set: usize,
+    pub messages: Vec<(String, String)>,
+}
+
+impl Default for JsonRowWriter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl JsonRowWriter {
+    pub fn new() -> Self {
+        let mut out = Vec::with_capacity(4096);
+        out.push(b'[');
+        Self {
+            out,
+            col_names: Vec::new(),
+            result_sets: 0,
+            rows_in_set: 0,
+            messages: Vec::new(),
+        }
+    }
+
+    fn begin_cell(&mut self, col: usize) {
+        if col == 0 {
+            if self.rows_in_set > 0 {
+                self.out.push(b',');
+            }
+            self.out.push(b'{');
+        } else {
+            self.out.push(b',');
+        }
+        self.out.push(b'"');
+        self.out
+            .extend_from_slice(json_escape(&self.col_names[col]).as_bytes());
+        self.out.extend_from_slice(b"\":");
+    }
+
+    fn push_str_literal(&mut self, s: &str) {
+        self.out.push(b'"');
+        self.out.extend_from_slice(json_escape(s).as_bytes());
+        self.out.push(b'"');
+    }
+
+    fn push_number(&mut self, s: String) {
+        self.out.extend_from_slice(s.as_bytes());
+    }
+
+    /// Close the in-progress buffer and return the finished JSON bytes.
+    pub fn finalize(mut self) -> Vec<u8> {
+        if self.result_sets > 0 {
+            self.out.push(b']'); // close the last result set's row array
+        }
+        self.out.push(b']'); // close the outer array
+        self.out
+    }
+}
+
+impl RowWriter for JsonRowWriter {
+    fn on_metadata(&mut self, columns: &[tabby::Column]) {
+        if self.result_sets > 0 {
+            self.out.push(b']');
+            self.out.push(b',');
+        }
+        self.out.push(b'[');
+        self.result_sets += 1;
+        self.rows_in_set = 0;
+        self.col_names = columns.iter().map(|c| c.name().to_string()).collect();
+    }
+
+    fn on_row_done(&mut self) {
+        self.out.push(b'}');
+        self.rows_in_set += 1;
+    }
+
+    fn on_info(&mut self, number: u32, message: &str) {
+        self.messages
+            .push((format!("[01000] ({})", number), message.to_owned()));
+    }
+
+    #[inline]
+    fn write_null(&mut self, col: usize) {
+        self.begin_cell(col);
+        self.out.extend_from_slice(b"null");
+    }
+    #[inline]
+    fn write_bool(&mut self, col: usize, val: bool) {
+        self.begin_cell(col);
+        self.out
+            .extend_from_slice(if val { b"true" } else { b"false" });
+    }
+    #[inline]
+    fn write_u8(&mut self, col: usize, val: u8) {
+        self.begin_cell(col);
+        self.push_number(val.to_string());
+    }
+    #[inline]
+    fn write_i16(&mut self, col: usize, val: i16) {
+        self.begin_cell(col);
+        self.push_number(val.to_string());
+    }
+    #[inline]
+    fn write_i32(&mut self, col: usize, val: i32) {
+        self.begin_cell(col);
+        self.push_number(val.to_string());
+    }
+    #[inline]
+    fn write_i64(&mut self, col: usize, val: i64) {
+        self.begin_cell(col);
+        self.push_number(val.to_string());
+    }
+    #[inline]
+    fn write_f32(&mut self, col: usize, val: f32) {
+        self.begin_cell(col);
+        self.push_number(if val.is_finite() {
+            (val as f64).to_string()
+        } else {
+            "null".to_string()
+        });
+    }
+    #[inline]
+    fn write_f64(&mut self, col: usize, val: f64) {
+        self.begin_cell(col);
+        self.push_number(if val.is_finite() {
+            val.to_string()
+        } else {
+            "null".to_string()
+        });
+    }
+    #[inline]
+    fn write_str(&mut self, col: usize, val: &str) {
+        self.begin_cell(col);
+        self.push_str_literal(val);
+    }
+    #[inline]
+    fn write_bytes(&mut self, col: usize, val: &[u8]) {
+        self.begin_cell(col);
+        self.out.push(b'"');
+        self.out.extend_from_slice(base64_encode(val).as_bytes());
+        self.out.push(b'"');
+    }
+    #[inline]
+    fn write_date(&mut self, col: usize, days: i32) {
+        self.begin_cell(col);
+        self.push_str_literal(&format_date_from_days(days));
+    }
+    #[inline]
+    fn write_time(&mut self, col: usize, nanos: i64) {
+        self.begin_cell(col);
+        self.push_str_literal(&format_time_from_nanos(nanos));
+    }
+    #[inline]
+    fn write_datetime(&mut self, col: usize, micros: i64) {
+        self.begin_cell(col);
+        let (year, month, day, hour, minute, second, remaining_micros) =
+            micros_to_components(micros);
+        let s = format_iso_nanos(
+            year,
+            month,
+            day,
+            hour,
+            minute,
+            second,
+            remaining_micros * 1000,
+            None,
+        );
+        self.push_str_literal(&s);
+    }
+    #[inline]
+    fn write_datetimeoffset(&mut self, col: usize, micros: i64, offset_minutes: i16) {
+        self.begin_cell(col);
+        let local_micros = micros + (offset_minutes as i64) * 60 * 1_000_000;
+        let (year, month, day, hour, minute, second, remaining_micros) =
+            micros_to_components(local_micros);
+        let s = format_iso_nanos(
+            year,
+            month,
+            day,
+            hour,
+            minute,
+            second,
+            remaining_micros * 1000,
+            Some(offset_minutes),
+        );
+        self.push_str_literal(&s);
+    }
+    #[inline]
+    fn write_decimal(&mut self, col: usize, value: i128, _precision: u8, scale: u8) {
+        self.begin_cell(col);
+        self.push_number(decimal_i128_to_string(value, scale));
+    }
+    #[inline]
+    fn write_guid(&mut self, col: usize, bytes: &[u8; 16]) {
+        self.begin_cell(col);
+        self.push_str_literal(&uuid::Uuid::from_bytes(*bytes).to_string());
+    }
+    #[inline]
+    fn write_utf16(&mut self, col: usize, val: &[u16]) {
+        self.begin_cell(col);
+        self.push_str_literal(&String::from_utf16_lossy(val));
+    }
+}
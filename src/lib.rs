@@ -1,9 +1,18 @@
 use pyo3::prelude::*;
 
+mod arrow_export;
+mod asyncio_api;
+mod cancel;
 mod connection;
 mod cursor;
+mod dialect;
 mod errors;
+mod json_export;
+mod numpy_export;
+mod pool;
+mod protobuf_export;
 pub mod row_writer;
+mod runtime;
 mod types;
 
 use connection::TdsConnection;
@@ -72,6 +81,10 @@ impl ParamInfo {
 #[pyclass]
 pub struct StatementHandle {
     pub cursor: TdsCursor,
+    /// `SQL_ATTR_METADATA_ID`, set via `DDBCSQLSetStmtAttr` — switches the
+    /// catalog functions' search-pattern arguments from `LIKE` wildcard
+    /// matching to exact identifier comparison. See `catalog_condition`.
+    metadata_id: bool,
 }
 
 #[pymethods]
@@ -79,6 +92,54 @@ impl StatementHandle {
     fn free(&mut self) -> PyResult<()> {
         self.cursor.close()
     }
+
+    /// Export the current result set as a `pyarrow.Table` instead of
+    /// `Vec<Vec<PyObject>>`, for analytics callers that would otherwise pay
+    /// per-cell `compact_value_to_py` boxing. Returns `None` if there is no
+    /// active result set.
+    fn fetch_arrow(&self, py: Python<'_>) -> PyResult<Option<PyObject>> {
+        self.cursor.fetch_arrow(py)
+    }
+
+    /// Export the current result set as a `dict[str, numpy.ndarray | list]`
+    /// instead of `Vec<Vec<PyObject>>`, for DataFrame-bound callers — see
+    /// [`crate::numpy_export`]. Returns `None` if there is no active result
+    /// set.
+    fn fetch_numpy(&self, py: Python<'_>) -> PyResult<Option<PyObject>> {
+        self.cursor.fetch_numpy(py)
+    }
+
+    /// Run `sql` as its own batch and return every result set serialized
+    /// straight to UTF-8 JSON bytes, via [`crate::json_export`]. Unlike
+    /// `fetch_arrow`/`fetch_numpy` this does not read back the cursor's
+    /// already-`execute`d result set — it issues a fresh batch so rows can
+    /// be serialized as they stream off the wire.
+    fn fetch_json(&mut self, py: Python<'_>, sql: &str) -> PyResult<Vec<u8>> {
+        self.cursor.fetch_json(py, sql)
+    }
+
+    /// Run `sql` as its own batch and return every result set encoded into
+    /// the framed protobuf-style stream from [`crate::protobuf_export`] — a
+    /// binary format an order of magnitude smaller than repr'd Python
+    /// objects, for non-Python consumers.
+    fn fetch_protobuf(&mut self, py: Python<'_>, sql: &str) -> PyResult<Vec<u8>> {
+        self.cursor.fetch_protobuf(py, sql)
+    }
+
+    /// Resolve `sql`'s result columns via `sp_describe_first_result_set`
+    /// without executing it. Read the result with `DDBCSQLDescribeCol`, the
+    /// same as after a live `execute`.
+    fn describe(&mut self, sql: &str) -> PyResult<()> {
+        self.cursor.describe(sql)
+    }
+
+    /// Request cancellation of whatever query is currently in flight on
+    /// this cursor's connection, from another Python thread. See
+    /// [`crate::cancel`] for why this currently reports `NotSupportedError`
+    /// rather than interrupting the query.
+    fn cancel(&self) -> PyResult<()> {
+        self.cursor.cancel()
+    }
 }
 
 #[pyclass(name = "Connection")]
@@ -115,9 +176,53 @@ impl PyConnection {
         self.inner.get_autocommit()
     }
 
+    /// Open a named savepoint within the current transaction
+    /// (`SAVE TRANSACTION <name>`).
+    fn savepoint(&mut self, name: &str) -> PyResult<()> {
+        self.inner.savepoint(name)
+    }
+    /// Roll back to a previously-opened savepoint, discarding any savepoints
+    /// opened after it; the named savepoint itself stays open.
+    fn rollback_to(&mut self, name: &str) -> PyResult<()> {
+        self.inner.rollback_to(name)
+    }
+    /// Forget a savepoint without rolling back to it.
+    fn release_savepoint(&mut self, name: &str) -> PyResult<()> {
+        self.inner.release_savepoint(name)
+    }
+    /// Set the isolation level (e.g. `"READ COMMITTED"`, `"SERIALIZABLE"`)
+    /// to apply to the next transaction this connection begins.
+    fn set_isolation_level(&mut self, level: &str) -> PyResult<()> {
+        self.inner.set_isolation_level(level)
+    }
+
+    /// Request cancellation of whatever query is currently in flight on
+    /// this connection, from another Python thread. See [`crate::cancel`]
+    /// for why this currently reports `NotSupportedError` rather than
+    /// interrupting the query.
+    fn cancel(&self) -> PyResult<()> {
+        self.inner.cancel()
+    }
+
     fn alloc_statement_handle(&mut self) -> PyResult<StatementHandle> {
         let cursor = self.inner.alloc_cursor()?;
-        Ok(StatementHandle { cursor })
+        Ok(StatementHandle {
+            cursor,
+            metadata_id: false,
+        })
+    }
+
+    /// Register a Python callable as the output converter for `sql_type`
+    /// (one of the codes `column_type_to_sql_type` produces). The callable
+    /// receives the value the driver would otherwise have returned and its
+    /// return value is surfaced to fetch callers instead.
+    fn register_output_converter(&self, sql_type: i32, func: PyObject) {
+        crate::types::register_output_converter(sql_type, func);
+    }
+
+    /// Remove every registered output converter.
+    fn clear_output_converters(&self) {
+        crate::types::clear_output_converters();
     }
 
     fn get_info(&self, info_type: u16) -> PyResult<Option<PyObject>> {
@@ -197,6 +302,28 @@ fn escape_sql(s: &str) -> String {
     s.replace('\'', "''")
 }
 
+/// Build a catalog-function WHERE condition for `column` against a single
+/// catalog/schema/table/column/procedure argument, honoring ODBC's
+/// `SQL_ATTR_METADATA_ID` search-pattern rules: `None` means "match
+/// everything" (the argument wasn't supplied) and is never turned into a
+/// condition; `Some(value)` is compared with `=` (identifier semantics, the
+/// argument taken literally) when `metadata_id` is set, or with `LIKE ...
+/// ESCAPE '\'` (`%`/`_` wildcards, `\` escapes a literal wildcard)
+/// otherwise, which is the default ODBC driver behavior SQLAlchemy's mssql
+/// reflection relies on for patterns like `schema='dbo%'`.
+fn catalog_condition(column: &str, value: Option<&str>, metadata_id: bool) -> Option<String> {
+    let value = value?;
+    if metadata_id {
+        Some(format!("{} = N'{}'", column, escape_sql(value)))
+    } else {
+        Some(format!(
+            "{} LIKE N'{}' ESCAPE '\\'",
+            column,
+            escape_sql(value)
+        ))
+    }
+}
+
 #[pyfunction]
 #[pyo3(signature = (stmt, sql, params, param_types, is_prepared, use_prepare))]
 #[pyo3(name = "DDBCSQLExecute")]
@@ -208,8 +335,16 @@ fn ddbc_sql_execute(
     is_prepared: &Bound<'_, pyo3::types::PyList>,
     use_prepare: bool,
 ) -> PyResult<i32> {
-    let _ = (param_types, is_prepared, use_prepare);
-    stmt.cursor.execute(sql, &params)
+    let sql = stmt.cursor.rewrite_dialect(sql);
+    let sql = sql.as_str();
+    if cursor::has_output_params(&param_types)? {
+        stmt.cursor
+            .execute_output_params(sql, &params, &param_types)
+    } else if use_prepare {
+        stmt.cursor.execute_prepared(sql, &params, is_prepared)
+    } else {
+        stmt.cursor.execute(sql, &params)
+    }
 }
 
 #[pyfunction]
@@ -297,9 +432,21 @@ fn ddbc_sql_more_results(stmt: &mut StatementHandle) -> PyResult<i32> {
     }
 }
 
+/// `SQL_ATTR_ROW_ARRAY_SIZE` from `sql.h` — one of the two statement
+/// attributes this driver acts on (with `SQL_ATTR_METADATA_ID` below);
+/// everything else is accepted and ignored.
+const SQL_ATTR_ROW_ARRAY_SIZE: i32 = 27;
+/// `SQL_ATTR_METADATA_ID` from `sql.h` — see `StatementHandle::metadata_id`.
+const SQL_ATTR_METADATA_ID: i32 = 10014;
+
 #[pyfunction]
 #[pyo3(name = "DDBCSQLSetStmtAttr")]
-fn ddbc_sql_set_stmt_attr(_stmt: &StatementHandle, _attr: i32, _value: i32) -> PyResult<i32> {
+fn ddbc_sql_set_stmt_attr(stmt: &mut StatementHandle, attr: i32, value: i32) -> PyResult<i32> {
+    if attr == SQL_ATTR_ROW_ARRAY_SIZE && value > 0 {
+        stmt.cursor.set_row_array_size(value as usize);
+    } else if attr == SQL_ATTR_METADATA_ID {
+        stmt.metadata_id = value != 0;
+    }
     Ok(0)
 }
 
@@ -316,25 +463,24 @@ fn ddbc_set_decimal_separator(_sep: &str) -> PyResult<()> {
 }
 
 #[pyfunction]
-#[pyo3(signature = (stmt, catalog, schema, table, types))]
+#[pyo3(signature = (stmt, catalog=None, schema=None, table=None, types=""))]
 #[pyo3(name = "DDBCSQLTables")]
 fn ddbc_sql_tables(
     stmt: &mut StatementHandle,
-    catalog: &str,
-    schema: &str,
-    table: &str,
+    catalog: Option<&str>,
+    schema: Option<&str>,
+    table: Option<&str>,
     types: &str,
 ) -> PyResult<i32> {
-    let mut conditions = Vec::new();
-    if !catalog.is_empty() {
-        conditions.push(format!("TABLE_CATALOG = N'{}'", escape_sql(catalog)));
-    }
-    if !schema.is_empty() {
-        conditions.push(format!("TABLE_SCHEMA LIKE N'{}'", escape_sql(schema)));
-    }
-    if !table.is_empty() {
-        conditions.push(format!("TABLE_NAME LIKE N'{}'", escape_sql(table)));
-    }
+    let metadata_id = stmt.metadata_id;
+    let mut conditions: Vec<String> = [
+        ("TABLE_CATALOG", catalog),
+        ("TABLE_SCHEMA", schema),
+        ("TABLE_NAME", table),
+    ]
+    .into_iter()
+    .filter_map(|(col, val)| catalog_condition(col, val, metadata_id))
+    .collect();
     if !types.is_empty() {
         let type_list: Vec<String> = types
             .split(',')
@@ -345,7 +491,7 @@ fn ddbc_sql_tables(
                 } else {
                     t
                 };
-                format!("N'{}'", mapped)
+                format!("N'{}'", escape_sql(mapped))
             })
             .collect();
         conditions.push(format!("TABLE_TYPE IN ({})", type_list.join(",")));
@@ -375,27 +521,16 @@ fn ddbc_sql_columns(
     table: Option<&str>,
     column: Option<&str>,
 ) -> PyResult<i32> {
-    let mut conditions = Vec::new();
-    if let Some(c) = catalog {
-        if !c.is_empty() {
-            conditions.push(format!("c.TABLE_CATALOG = N'{}'", escape_sql(c)));
-        }
-    }
-    if let Some(s) = schema {
-        if !s.is_empty() {
-            conditions.push(format!("c.TABLE_SCHEMA LIKE N'{}'", escape_sql(s)));
-        }
-    }
-    if let Some(t) = table {
-        if !t.is_empty() {
-            conditions.push(format!("c.TABLE_NAME LIKE N'{}'", escape_sql(t)));
-        }
-    }
-    if let Some(col) = column {
-        if !col.is_empty() {
-            conditions.push(format!("c.COLUMN_NAME LIKE N'{}'", escape_sql(col)));
-        }
-    }
+    let metadata_id = stmt.metadata_id;
+    let conditions: Vec<String> = [
+        ("c.TABLE_CATALOG", catalog),
+        ("c.TABLE_SCHEMA", schema),
+        ("c.TABLE_NAME", table),
+        ("c.COLUMN_NAME", column),
+    ]
+    .into_iter()
+    .filter_map(|(col, val)| catalog_condition(col, val, metadata_id))
+    .collect();
     let where_clause = if conditions.is_empty() {
         String::new()
     } else {
@@ -415,7 +550,7 @@ fn ddbc_sql_columns(
            WHEN 'date' THEN 91 WHEN 'time' THEN 92 WHEN 'datetimeoffset' THEN -155 \
            WHEN 'uniqueidentifier' THEN -11 WHEN 'xml' THEN -152 \
            ELSE 0 END AS data_type, \
-         c.DATA_TYPE AS type_name, \
+         COALESCE(ut.name, c.DATA_TYPE) AS type_name, \
          COALESCE(c.CHARACTER_MAXIMUM_LENGTH, c.NUMERIC_PRECISION, \
            CASE c.DATA_TYPE WHEN 'datetime' THEN 23 WHEN 'smalldatetime' THEN 16 WHEN 'datetime2' THEN 27 \
            WHEN 'date' THEN 10 WHEN 'time' THEN 16 WHEN 'datetimeoffset' THEN 34 \
@@ -444,7 +579,11 @@ fn ddbc_sql_columns(
          c.ORDINAL_POSITION AS ordinal_position, \
          c.IS_NULLABLE AS is_nullable, \
          CAST(NULL AS INT) AS ss_data_type \
-         FROM INFORMATION_SCHEMA.COLUMNS c {} \
+         FROM INFORMATION_SCHEMA.COLUMNS c \
+         LEFT JOIN sys.columns sc ON sc.object_id = OBJECT_ID(QUOTENAME(c.TABLE_SCHEMA) + '.' + QUOTENAME(c.TABLE_NAME)) \
+           AND sc.name = c.COLUMN_NAME \
+         LEFT JOIN sys.types ut ON sc.user_type_id = ut.user_type_id AND ut.is_user_defined = 1 \
+         {} \
          ORDER BY table_cat, table_schem, table_name, ordinal_position",
         where_clause
     );
@@ -619,22 +758,15 @@ fn ddbc_sql_procedures(
     schema: Option<&str>,
     procedure: Option<&str>,
 ) -> PyResult<i32> {
-    let mut conditions = Vec::new();
-    if let Some(c) = catalog {
-        if !c.is_empty() {
-            conditions.push(format!("ROUTINE_CATALOG = N'{}'", escape_sql(c)));
-        }
-    }
-    if let Some(s) = schema {
-        if !s.is_empty() {
-            conditions.push(format!("ROUTINE_SCHEMA LIKE N'{}'", escape_sql(s)));
-        }
-    }
-    if let Some(p) = procedure {
-        if !p.is_empty() {
-            conditions.push(format!("ROUTINE_NAME LIKE N'{}'", escape_sql(p)));
-        }
-    }
+    let metadata_id = stmt.metadata_id;
+    let conditions: Vec<String> = [
+        ("ROUTINE_CATALOG", catalog),
+        ("ROUTINE_SCHEMA", schema),
+        ("ROUTINE_NAME", procedure),
+    ]
+    .into_iter()
+    .filter_map(|(col, val)| catalog_condition(col, val, metadata_id))
+    .collect();
     let where_clause = if conditions.is_empty() {
         String::new()
     } else {
@@ -653,14 +785,82 @@ fn ddbc_sql_procedures(
 }
 
 #[pyfunction]
-#[pyo3(signature = (stmt, id_type, catalog=None, schema=None, table="", _scope=0, _nullable=0))]
+#[pyo3(signature = (stmt, catalog=None, schema=None, procedure=None, column=None))]
+#[pyo3(name = "DDBCSQLProcedureColumns")]
+fn ddbc_sql_procedure_columns(
+    stmt: &mut StatementHandle,
+    catalog: Option<&str>,
+    schema: Option<&str>,
+    procedure: Option<&str>,
+    column: Option<&str>,
+) -> PyResult<i32> {
+    let metadata_id = stmt.metadata_id;
+    let conditions: Vec<String> = [
+        ("p.SPECIFIC_CATALOG", catalog),
+        ("p.SPECIFIC_SCHEMA", schema),
+        ("p.SPECIFIC_NAME", procedure),
+        ("p.PARAMETER_NAME", column),
+    ]
+    .into_iter()
+    .filter_map(|(col, val)| catalog_condition(col, val, metadata_id))
+    .collect();
+    let where_clause = if conditions.is_empty() {
+        String::new()
+    } else {
+        format!("WHERE {}", conditions.join(" AND "))
+    };
+    let sql = format!(
+        "SELECT p.SPECIFIC_CATALOG AS procedure_cat, p.SPECIFIC_SCHEMA AS procedure_schem, \
+         p.SPECIFIC_NAME AS procedure_name, \
+         COALESCE(p.PARAMETER_NAME, '') AS column_name, \
+         CASE WHEN p.ORDINAL_POSITION = 0 THEN 5 \
+           ELSE CASE p.PARAMETER_MODE WHEN 'IN' THEN 1 WHEN 'INOUT' THEN 2 WHEN 'OUT' THEN 4 ELSE 1 END \
+           END AS column_type, \
+         CASE p.DATA_TYPE \
+           WHEN 'int' THEN 4 WHEN 'bigint' THEN -5 WHEN 'smallint' THEN 5 WHEN 'tinyint' THEN -6 \
+           WHEN 'bit' THEN -7 WHEN 'float' THEN 6 WHEN 'real' THEN 7 \
+           WHEN 'decimal' THEN 3 WHEN 'numeric' THEN 2 WHEN 'money' THEN 3 WHEN 'smallmoney' THEN 3 \
+           WHEN 'char' THEN 1 WHEN 'varchar' THEN 12 WHEN 'text' THEN -1 \
+           WHEN 'nchar' THEN -8 WHEN 'nvarchar' THEN -9 WHEN 'ntext' THEN -10 \
+           WHEN 'binary' THEN -2 WHEN 'varbinary' THEN -3 WHEN 'image' THEN -4 \
+           WHEN 'datetime' THEN 93 WHEN 'smalldatetime' THEN 93 WHEN 'datetime2' THEN 93 \
+           WHEN 'date' THEN 91 WHEN 'time' THEN 92 WHEN 'datetimeoffset' THEN -155 \
+           WHEN 'uniqueidentifier' THEN -11 WHEN 'xml' THEN -152 \
+           ELSE 0 END AS data_type, \
+         p.DATA_TYPE AS type_name, \
+         COALESCE(p.CHARACTER_MAXIMUM_LENGTH, p.NUMERIC_PRECISION, 0) AS column_size, \
+         COALESCE(p.CHARACTER_OCTET_LENGTH, p.NUMERIC_PRECISION, 0) AS buffer_length, \
+         p.NUMERIC_SCALE AS decimal_digits, \
+         CASE WHEN p.NUMERIC_PRECISION_RADIX IS NOT NULL THEN 10 ELSE NULL END AS num_prec_radix, \
+         2 AS nullable, \
+         CAST(NULL AS VARCHAR(254)) AS remarks, \
+         CAST(NULL AS VARCHAR(254)) AS column_def, \
+         CASE p.DATA_TYPE \
+           WHEN 'datetime' THEN 9 WHEN 'smalldatetime' THEN 9 WHEN 'datetime2' THEN 9 \
+           WHEN 'date' THEN 9 WHEN 'time' THEN 9 WHEN 'datetimeoffset' THEN 9 \
+           ELSE 0 END AS sql_data_type, \
+         CASE p.DATA_TYPE \
+           WHEN 'datetime' THEN 3 WHEN 'smalldatetime' THEN 0 WHEN 'datetime2' THEN 7 \
+           WHEN 'time' THEN 7 WHEN 'datetimeoffset' THEN 7 ELSE NULL END AS sql_datetime_sub, \
+         p.CHARACTER_OCTET_LENGTH AS char_octet_length, \
+         p.ORDINAL_POSITION AS ordinal_position, \
+         '' AS is_nullable \
+         FROM INFORMATION_SCHEMA.PARAMETERS p {} \
+         ORDER BY procedure_cat, procedure_schem, procedure_name, ordinal_position",
+        where_clause
+    );
+    stmt.cursor.execute(&sql, &[])
+}
+
+#[pyfunction]
+#[pyo3(signature = (stmt, id_type, catalog=None, schema=None, table=None, _scope=0, _nullable=0))]
 #[pyo3(name = "DDBCSQLSpecialColumns")]
 fn ddbc_sql_special_columns(
     stmt: &mut StatementHandle,
     id_type: i32,
     catalog: Option<&str>,
     schema: Option<&str>,
-    table: &str,
+    table: Option<&str>,
     _scope: i32,
     _nullable: i32,
 ) -> PyResult<i32> {
@@ -669,20 +869,15 @@ fn ddbc_sql_special_columns(
         return stmt.cursor.execute(sql, &[]);
     }
 
-    let mut conditions = Vec::new();
-    if let Some(c) = catalog {
-        if !c.is_empty() {
-            conditions.push(format!("DB_NAME() = N'{}'", escape_sql(c)));
-        }
-    }
-    if let Some(s) = schema {
-        if !s.is_empty() {
-            conditions.push(format!("s.name = N'{}'", escape_sql(s)));
-        }
-    }
-    if !table.is_empty() {
-        conditions.push(format!("t.name = N'{}'", escape_sql(table)));
-    }
+    let metadata_id = stmt.metadata_id;
+    let conditions: Vec<String> = [
+        ("DB_NAME()", catalog),
+        ("s.name", schema),
+        ("t.name", table),
+    ]
+    .into_iter()
+    .filter_map(|(col, val)| catalog_condition(col, val, metadata_id))
+    .collect();
     let where_clause = if conditions.is_empty() {
         "WHERE 1=1".to_string()
     } else {
@@ -692,21 +887,22 @@ fn ddbc_sql_special_columns(
     let sql = if id_type == 2 {
         format!(
             "SELECT 2 AS scope, c.name AS column_name, \
-             CASE tp.name WHEN 'timestamp' THEN -2 WHEN 'rowversion' THEN -2 ELSE 0 END AS data_type, \
+             CASE base_tp.name WHEN 'timestamp' THEN -2 WHEN 'rowversion' THEN -2 ELSE 0 END AS data_type, \
              tp.name AS type_name, c.max_length AS column_size, c.max_length AS buffer_length, \
              0 AS decimal_digits, 1 AS pseudo_column \
              FROM sys.tables t \
              JOIN sys.schemas s ON t.schema_id = s.schema_id \
              JOIN sys.columns c ON t.object_id = c.object_id \
              JOIN sys.types tp ON c.system_type_id = tp.system_type_id AND c.user_type_id = tp.user_type_id \
-             {} AND tp.name IN ('timestamp', 'rowversion') \
+             JOIN sys.types base_tp ON c.system_type_id = base_tp.system_type_id AND base_tp.is_user_defined = 0 \
+             {} AND base_tp.name IN ('timestamp', 'rowversion') \
              ORDER BY scope",
             where_clause
         )
     } else {
         format!(
             "SELECT 2 AS scope, c.name AS column_name, \
-             CASE tp.name \
+             CASE base_tp.name \
                WHEN 'int' THEN 4 WHEN 'bigint' THEN -5 WHEN 'smallint' THEN 5 WHEN 'tinyint' THEN -6 \
                WHEN 'uniqueidentifier' THEN -11 WHEN 'nvarchar' THEN -9 WHEN 'varchar' THEN 12 \
                ELSE 0 END AS data_type, \
@@ -721,6 +917,7 @@ fn ddbc_sql_special_columns(
              JOIN sys.index_columns ic ON i.object_id = ic.object_id AND i.index_id = ic.index_id \
              JOIN sys.columns c ON ic.object_id = c.object_id AND ic.column_id = c.column_id \
              JOIN sys.types tp ON c.system_type_id = tp.system_type_id AND c.user_type_id = tp.user_type_id \
+             JOIN sys.types base_tp ON c.system_type_id = base_tp.system_type_id AND base_tp.is_user_defined = 0 \
              {} ORDER BY scope",
             where_clause
         )
@@ -728,14 +925,72 @@ fn ddbc_sql_special_columns(
     stmt.cursor.execute(&sql, &[])
 }
 
+/// Shared `CASE <name_expr> WHEN ... END` driving `ddbc_sql_get_type_info`'s
+/// `data_type` column, parameterized over which column holds the system
+/// type name so the same mapping can run against a base type's own name or
+/// (for a user-defined alias row) the base type it was declared `FROM`.
+fn type_info_data_type_case(name_expr: &str) -> String {
+    format!(
+        "CASE {name} \
+           WHEN 'int' THEN 4 WHEN 'bigint' THEN -5 WHEN 'smallint' THEN 5 WHEN 'tinyint' THEN -6 \
+           WHEN 'bit' THEN -7 WHEN 'float' THEN 6 WHEN 'real' THEN 7 \
+           WHEN 'decimal' THEN 3 WHEN 'numeric' THEN 2 WHEN 'money' THEN 3 WHEN 'smallmoney' THEN 3 \
+           WHEN 'char' THEN 1 WHEN 'varchar' THEN 12 WHEN 'text' THEN -1 \
+           WHEN 'nchar' THEN -8 WHEN 'nvarchar' THEN -9 WHEN 'ntext' THEN -10 \
+           WHEN 'binary' THEN -2 WHEN 'varbinary' THEN -3 WHEN 'image' THEN -4 \
+           WHEN 'datetime' THEN 93 WHEN 'smalldatetime' THEN 93 WHEN 'datetime2' THEN 93 \
+           WHEN 'date' THEN 91 WHEN 'time' THEN 92 WHEN 'datetimeoffset' THEN -155 \
+           WHEN 'uniqueidentifier' THEN -11 WHEN 'xml' THEN -152 \
+           ELSE 0 END",
+        name = name_expr
+    )
+}
+
+/// Shared `CASE <name_expr> WHEN ... END` driving `ddbc_sql_get_type_info`'s
+/// `sql_data_type` column — see [`type_info_data_type_case`].
+fn type_info_sql_data_type_case(name_expr: &str) -> String {
+    format!(
+        "CASE {name} \
+           WHEN 'datetime' THEN 9 WHEN 'smalldatetime' THEN 9 WHEN 'datetime2' THEN 9 \
+           WHEN 'date' THEN 9 WHEN 'time' THEN 9 WHEN 'datetimeoffset' THEN 9 \
+           ELSE {data_type} END",
+        name = name_expr,
+        data_type = type_info_data_type_case(name_expr)
+    )
+}
+
+/// `include_user_defined_types` additionally reports one row per
+/// `CREATE TYPE ... FROM <base type>` alias (and other user-defined scalar
+/// types in `sys.types`), inheriting its base type's `data_type`, precision,
+/// literal prefix/suffix, and `create_params`, with `local_type_name` set to
+/// the alias's own name. Off by default since most callers only expect the
+/// builtin system types this function already returned.
 #[pyfunction]
+#[pyo3(signature = (stmt, sql_type, include_user_defined_types = false))]
 #[pyo3(name = "DDBCSQLGetTypeInfo")]
-fn ddbc_sql_get_type_info(stmt: &mut StatementHandle, sql_type: i32) -> PyResult<i32> {
+fn ddbc_sql_get_type_info(
+    stmt: &mut StatementHandle,
+    sql_type: i32,
+    include_user_defined_types: bool,
+) -> PyResult<i32> {
     let type_filter = if sql_type == 0 {
         String::new()
     } else {
         format!("WHERE data_type = {}", sql_type)
     };
+    let user_defined_rows = if include_user_defined_types {
+        format!(
+            " UNION ALL \
+             SELECT bt.name AS type_name, ut.name AS local_type_name, {data_type} AS data_type, {sql_data_type} AS sql_data_type \
+             FROM sys.types ut \
+             JOIN sys.types bt ON ut.system_type_id = bt.user_type_id AND bt.is_user_defined = 0 \
+             WHERE ut.is_user_defined = 1",
+            data_type = type_info_data_type_case("bt.name"),
+            sql_data_type = type_info_sql_data_type_case("bt.name"),
+        )
+    } else {
+        String::new()
+    };
     let sql = format!(
         "SELECT type_name, data_type, CASE type_name \
            WHEN 'bigint' THEN 19 WHEN 'int' THEN 10 WHEN 'smallint' THEN 5 WHEN 'tinyint' THEN 3 \
@@ -764,76 +1019,154 @@ fn ddbc_sql_get_type_info(stmt: &mut StatementHandle, sql_type: i32) -> PyResult
          CASE WHEN type_name IN ('tinyint','bit') THEN 1 ELSE 0 END AS unsigned_attribute, \
          0 AS fixed_prec_scale, \
          CASE WHEN type_name IN ('int','bigint','smallint','tinyint','decimal','numeric') THEN 1 ELSE 0 END AS auto_unique_value, \
-         type_name AS local_type_name, \
+         local_type_name, \
          0 AS minimum_scale, \
          CASE type_name WHEN 'decimal' THEN 38 WHEN 'numeric' THEN 38 WHEN 'datetime2' THEN 7 WHEN 'time' THEN 7 ELSE 0 END AS maximum_scale, \
          data_type AS sql_data_type, \
          CAST(NULL AS SMALLINT) AS sql_datetime_sub, \
          CASE WHEN type_name IN ('decimal','numeric') THEN 10 ELSE NULL END AS num_prec_radix, \
          0 AS interval_precision \
-         FROM (SELECT name AS type_name, \
-           CASE name \
-             WHEN 'int' THEN 4 WHEN 'bigint' THEN -5 WHEN 'smallint' THEN 5 WHEN 'tinyint' THEN -6 \
-             WHEN 'bit' THEN -7 WHEN 'float' THEN 6 WHEN 'real' THEN 7 \
-             WHEN 'decimal' THEN 3 WHEN 'numeric' THEN 2 WHEN 'money' THEN 3 WHEN 'smallmoney' THEN 3 \
-             WHEN 'char' THEN 1 WHEN 'varchar' THEN 12 WHEN 'text' THEN -1 \
-             WHEN 'nchar' THEN -8 WHEN 'nvarchar' THEN -9 WHEN 'ntext' THEN -10 \
-             WHEN 'binary' THEN -2 WHEN 'varbinary' THEN -3 WHEN 'image' THEN -4 \
-             WHEN 'datetime' THEN 93 WHEN 'smalldatetime' THEN 93 WHEN 'datetime2' THEN 93 \
-             WHEN 'date' THEN 91 WHEN 'time' THEN 92 WHEN 'datetimeoffset' THEN -155 \
-             WHEN 'uniqueidentifier' THEN -11 WHEN 'xml' THEN -152 \
-             ELSE 0 END AS data_type, \
-           CASE name \
-             WHEN 'datetime' THEN 9 WHEN 'smalldatetime' THEN 9 WHEN 'datetime2' THEN 9 \
-             WHEN 'date' THEN 9 WHEN 'time' THEN 9 WHEN 'datetimeoffset' THEN 9 \
-             ELSE CASE name WHEN 'int' THEN 4 WHEN 'bigint' THEN -5 WHEN 'smallint' THEN 5 WHEN 'tinyint' THEN -6 \
-               WHEN 'bit' THEN -7 WHEN 'float' THEN 6 WHEN 'real' THEN 7 \
-               WHEN 'decimal' THEN 3 WHEN 'numeric' THEN 2 WHEN 'money' THEN 3 WHEN 'smallmoney' THEN 3 \
-               WHEN 'char' THEN 1 WHEN 'varchar' THEN 12 WHEN 'text' THEN -1 \
-               WHEN 'nchar' THEN -8 WHEN 'nvarchar' THEN -9 WHEN 'ntext' THEN -10 \
-               WHEN 'binary' THEN -2 WHEN 'varbinary' THEN -3 WHEN 'image' THEN -4 \
-               WHEN 'uniqueidentifier' THEN -11 WHEN 'xml' THEN -152 ELSE 0 END END AS sql_data_type \
-         FROM sys.types WHERE is_user_defined = 0 AND name != 'sysname') AS t {} ORDER BY data_type",
-        type_filter
+         FROM (SELECT name AS type_name, name AS local_type_name, {base_data_type} AS data_type, \
+           {base_sql_data_type} AS sql_data_type \
+         FROM sys.types WHERE is_user_defined = 0 AND name != 'sysname'{user_defined_rows}) AS t {type_filter} \
+         ORDER BY data_type, type_name",
+        base_data_type = type_info_data_type_case("name"),
+        base_sql_data_type = type_info_sql_data_type_case("name"),
+        user_defined_rows = user_defined_rows,
+        type_filter = type_filter,
     );
     stmt.cursor.execute(&sql, &[])
 }
 
+/// ODBC API function IDs, as defined by `SQLGetFunctions`/`sql.h`'s
+/// `SQL_API_SQL*` constants. `ALL_FUNCTIONS` is the legacy ODBC 2.x
+/// "report every function" id; `ODBC3_ALL_FUNCTIONS`/`ODBC3_ALL_FUNCTIONS_SIZE`
+/// are its ODBC 3.x replacement, a 4000-bit conformance bitmap.
+const SQL_API_ALL_FUNCTIONS: i32 = 0;
+const SQL_API_ODBC3_ALL_FUNCTIONS: i32 = 999;
+const SQL_API_ODBC3_ALL_FUNCTIONS_SIZE: i32 = 4000;
+
+/// Whether this crate actually implements the ODBC API function `function_id`
+/// names — i.e. whether a `DDBCSQL*` function above backs it, as opposed to
+/// a driver manager needing to emulate it or report `SQL_ERROR`. Limited to
+/// the handful of `SQL_API_SQL*` codes corresponding to functions this crate
+/// exposes; update alongside new `DDBCSQL*` additions.
+fn ddbc_function_supported(function_id: i32) -> bool {
+    matches!(
+        function_id,
+        8 |    // SQL_API_SQLDESCRIBECOL
+        11 |   // SQL_API_SQLEXECDIRECT
+        12 |   // SQL_API_SQLEXECUTE
+        13 |   // SQL_API_SQLFETCH
+        18 |   // SQL_API_SQLNUMRESULTCOLS
+        19 |   // SQL_API_SQLPREPARE
+        20 |   // SQL_API_SQLROWCOUNT
+        40 |   // SQL_API_SQLCOLUMNS
+        44 |   // SQL_API_SQLGETFUNCTIONS
+        45 |   // SQL_API_SQLGETINFO
+        47 |   // SQL_API_SQLGETTYPEINFO
+        52 |   // SQL_API_SQLSPECIALCOLUMNS
+        53 |   // SQL_API_SQLSTATISTICS
+        54 |   // SQL_API_SQLTABLES
+        60 |   // SQL_API_SQLFOREIGNKEYS
+        61 |   // SQL_API_SQLMORERESULTS
+        65 |   // SQL_API_SQLPRIMARYKEYS
+        66 |   // SQL_API_SQLPROCEDURECOLUMNS
+        67 |   // SQL_API_SQLPROCEDURES
+        72 |   // SQL_API_SQLBINDPARAMETER
+        1021 // SQL_API_SQLFETCHSCROLL
+    )
+}
+
+/// Report whether this driver implements the ODBC API function `function_id`
+/// names, for front-ends that feature-detect instead of probing catalog
+/// functions by trial and error. `function_id` of `SQL_API_ALL_FUNCTIONS`
+/// (`0`) or `SQL_API_ODBC3_ALL_FUNCTIONS` (`999`) returns the full
+/// conformance bitmap as a `list[bool]` instead of a single bool.
+#[pyfunction]
+#[pyo3(name = "DDBCSQLGetFunctions")]
+fn ddbc_sql_get_functions(function_id: i32) -> PyResult<PyObject> {
+    Python::with_gil(|py| {
+        if function_id == SQL_API_ALL_FUNCTIONS || function_id == SQL_API_ODBC3_ALL_FUNCTIONS {
+            let bitmap: Vec<bool> = (0..SQL_API_ODBC3_ALL_FUNCTIONS_SIZE)
+                .map(ddbc_function_supported)
+                .collect();
+            let list = pyo3::types::PyList::new(py, &bitmap)?;
+            return Ok(list.into_any().unbind());
+        }
+        Ok(ddbc_function_supported(function_id)
+            .into_pyobject(py)
+            .unwrap()
+            .into_any()
+            .unbind())
+    })
+}
+
+/// ODBC `SQLFetchScroll` orientation codes (`SQL_FETCH_*` from `sql.h`).
+const SQL_FETCH_NEXT: i32 = 1;
+const SQL_FETCH_FIRST: i32 = 2;
+const SQL_FETCH_LAST: i32 = 3;
+const SQL_FETCH_PRIOR: i32 = 4;
+const SQL_FETCH_ABSOLUTE: i32 = 5;
+const SQL_FETCH_RELATIVE: i32 = 6;
+const SQL_FETCH_BOOKMARK: i32 = 8;
+
 #[pyfunction]
 #[pyo3(name = "DDBCSQLFetchScroll")]
 fn ddbc_sql_fetch_scroll(
     stmt: &mut StatementHandle,
     orientation: i32,
     offset: i64,
-    _row_data: &Bound<'_, pyo3::types::PyList>,
+    row_data: &Bound<'_, pyo3::types::PyList>,
 ) -> PyResult<i32> {
     let total_rows = stmt.cursor.row_count_total();
-    match orientation {
-        6 => {
-            // SQL_FETCH_RELATIVE
-            let new_pos = stmt.cursor.current_row_index() as i64 + offset;
-            if new_pos < 0 || new_pos >= total_rows as i64 {
-                return Ok(100);
-            }
-            stmt.cursor.set_row_index(new_pos as usize);
-            Ok(0)
-        }
-        5 => {
-            // SQL_FETCH_ABSOLUTE
-            if offset < 0 || total_rows == 0 {
-                stmt.cursor.set_row_index(0);
-                return Ok(if total_rows == 0 { 100 } else { 0 });
-            }
-            if offset as usize >= total_rows {
-                return Ok(100);
+    let rowset_size = stmt.cursor.row_array_size();
+    let current = stmt.cursor.current_row_index() as i64;
+
+    let new_pos: i64 = match orientation {
+        SQL_FETCH_NEXT => current + rowset_size as i64,
+        SQL_FETCH_FIRST => 0,
+        SQL_FETCH_LAST => {
+            if total_rows <= rowset_size {
+                0
+            } else {
+                (total_rows - rowset_size) as i64
             }
-            stmt.cursor.set_row_index(offset as usize);
-            Ok(0)
         }
-        _ => Ok(100),
+        SQL_FETCH_PRIOR => current - rowset_size as i64,
+        SQL_FETCH_ABSOLUTE => offset,
+        SQL_FETCH_RELATIVE => current + offset,
+        // This driver has no real ODBC bookmark (byte-value) storage, so
+        // "bookmark" here is just the cursor's current row index — the same
+        // position-based approximation SQL_FETCH_RELATIVE above uses.
+        SQL_FETCH_BOOKMARK => current + offset,
+        _ => return Ok(100),
+    };
+
+    if new_pos < 0 {
+        // Covers both SQL_FETCH_ABSOLUTE's negative-offset convention and
+        // SQL_FETCH_PRIOR walking back past the start of the result set.
+        stmt.cursor.set_row_index(0);
+        return Ok(100);
     }
+    if new_pos as usize >= total_rows {
+        return Ok(100);
+    }
+
+    let start = new_pos as usize;
+    stmt.cursor.set_row_index(start);
+    let py = row_data.py();
+    let fetched = stmt
+        .cursor
+        .fetch_rowset_into(py, start, rowset_size, row_data)?;
+    Ok(if fetched == 0 { 100 } else { 0 })
 }
 
+/// Returns `(rows_affected, row_statuses)` — `row_statuses` is one
+/// `SQL_PARAM_SUCCESS`/`SQL_PARAM_ERROR` entry per row of
+/// `columnwise_params`, letting a caller map a partial-batch failure back to
+/// the specific rows that failed. Diagnostics for failed rows are readable
+/// via `DDBCSQLGetAllDiagRecords`.
 #[pyfunction]
 #[pyo3(signature = (stmt, sql, columnwise_params, param_types, row_count))]
 #[pyo3(name = "SQLExecuteMany")]
@@ -843,15 +1176,24 @@ fn sql_execute_many(
     columnwise_params: Vec<Bound<'_, pyo3::types::PyList>>,
     param_types: Vec<Bound<'_, PyAny>>,
     row_count: usize,
-) -> PyResult<i32> {
-    let _ = param_types;
-    stmt.cursor.execute_many(sql, &columnwise_params, row_count)
+) -> PyResult<(i64, Vec<i32>)> {
+    let sql = stmt.cursor.rewrite_dialect(sql);
+    stmt.cursor
+        .execute_many(&sql, &columnwise_params, &param_types, row_count)
 }
 
 #[pymodule]
 fn whiskers_native(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    // Let `pyo3_asyncio::tokio::future_into_py` drive coroutines on the same
+    // shared runtime `py.allow_threads(|| runtime::block_on(...))` already
+    // uses, instead of spinning up a second Tokio runtime of its own.
+    pyo3_asyncio::tokio::init_with_runtime(&runtime::RUNTIME)
+        .expect("failed to install shared Tokio runtime for the asyncio bridge");
+
     m.add_class::<PyConnection>()?;
     m.add_class::<StatementHandle>()?;
+    m.add_class::<asyncio_api::AsyncConnection>()?;
+    m.add_class::<asyncio_api::AsyncCursor>()?;
     m.add_class::<NumericData>()?;
     m.add_class::<ParamInfo>()?;
     m.add_function(wrap_pyfunction!(ddbc_sql_execute, m)?)?;
@@ -870,8 +1212,10 @@ fn whiskers_native(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_function(wrap_pyfunction!(ddbc_sql_foreign_keys, m)?)?;
     m.add_function(wrap_pyfunction!(ddbc_sql_statistics, m)?)?;
     m.add_function(wrap_pyfunction!(ddbc_sql_procedures, m)?)?;
+    m.add_function(wrap_pyfunction!(ddbc_sql_procedure_columns, m)?)?;
     m.add_function(wrap_pyfunction!(ddbc_sql_special_columns, m)?)?;
     m.add_function(wrap_pyfunction!(ddbc_sql_get_type_info, m)?)?;
+    m.add_function(wrap_pyfunction!(ddbc_sql_get_functions, m)?)?;
     m.add_function(wrap_pyfunction!(ddbc_sql_fetch_scroll, m)?)?;
     m.add_function(wrap_pyfunction!(sql_execute_many, m)?)?;
     Ok(())
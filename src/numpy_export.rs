@@ -0,0 +1,110 @@
+//! Zero-copy-ish columnar NumPy export for bulk fetches.
+//!
+//! Same shape as [`crate::arrow_export`]: `PyRowWriter.values` is already
+//! column-major-addressable, so each column is walked once into a
+//! contiguous typed Rust buffer (plus a null mask) instead of boxing every
+//! cell into a `PyObject` via `compact_value_to_py`. Homogeneous numeric
+//! columns become `int64`/`float64` NumPy arrays built straight from the
+//! backing `Vec` (no per-element boxing); a nullable integer column falls
+//! back to `float64` with `NaN` since NumPy has no native nullable int
+//! dtype; strings/decimals/GUIDs/binary stay as plain Python object lists,
+//! the same degrade path `arrow_export` uses for SQL_VARIANT/XML.
+
+use numpy::PyArray1;
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyList};
+
+use crate::cursor::ColumnInfo;
+use crate::row_writer::{CompactValue, PyRowWriter};
+use crate::types::compact_value_to_py;
+
+enum NumpyColumn {
+    I64 { data: Vec<i64>, valid: Vec<bool> },
+    F64 { data: Vec<f64> },
+    Object { data: Vec<CompactValue> },
+}
+
+impl NumpyColumn {
+    fn new(sql_type: i32, capacity: usize) -> Self {
+        match sql_type {
+            4 | 5 | -6 | -5 => NumpyColumn::I64 {
+                data: Vec::with_capacity(capacity),
+                valid: Vec::with_capacity(capacity),
+            },
+            6 | 7 | 8 => NumpyColumn::F64 {
+                data: Vec::with_capacity(capacity),
+            },
+            _ => NumpyColumn::Object {
+                data: Vec::with_capacity(capacity),
+            },
+        }
+    }
+
+    fn push(&mut self, val: &CompactValue) {
+        match (self, val) {
+            (NumpyColumn::I64 { data, valid }, CompactValue::I64(v)) => {
+                data.push(*v);
+                valid.push(true);
+            }
+            (NumpyColumn::I64 { data, valid }, _) => {
+                data.push(0);
+                valid.push(false);
+            }
+            (NumpyColumn::F64 { data }, CompactValue::F64(v)) => data.push(*v),
+            (NumpyColumn::F64 { data }, _) => data.push(f64::NAN),
+            (NumpyColumn::Object { data }, v) => data.push(v.clone()),
+        }
+    }
+
+    fn finish(self, py: Python<'_>) -> PyResult<PyObject> {
+        match self {
+            NumpyColumn::I64 { data, valid } => {
+                if valid.iter().all(|ok| *ok) {
+                    Ok(PyArray1::from_vec(py, data).into_any().unbind())
+                } else {
+                    // Degrade to float64+NaN — NumPy has no native nullable int array.
+                    let floats: Vec<f64> = data
+                        .into_iter()
+                        .zip(valid)
+                        .map(|(v, ok)| if ok { v as f64 } else { f64::NAN })
+                        .collect();
+                    Ok(PyArray1::from_vec(py, floats).into_any().unbind())
+                }
+            }
+            NumpyColumn::F64 { data } => Ok(PyArray1::from_vec(py, data).into_any().unbind()),
+            NumpyColumn::Object { data } => {
+                let objs: Vec<PyObject> = data
+                    .iter()
+                    .map(|v| compact_value_to_py(py, v))
+                    .collect::<PyResult<_>>()?;
+                Ok(PyList::new(py, objs)?.into_any().unbind())
+            }
+        }
+    }
+}
+
+/// Convert a decoded result set into a `dict[str, numpy.ndarray | list]`
+/// keyed by column name, one pass over `writer.values` per column.
+pub fn writer_to_numpy_columns(
+    py: Python<'_>,
+    writer: &PyRowWriter,
+    columns: &[ColumnInfo],
+) -> PyResult<PyObject> {
+    let row_count = writer.row_count();
+    let mut builders: Vec<NumpyColumn> = columns
+        .iter()
+        .map(|c| NumpyColumn::new(c.sql_type, row_count))
+        .collect();
+
+    for (col, builder) in builders.iter_mut().enumerate() {
+        for row in 0..row_count {
+            builder.push(writer.get(row, col));
+        }
+    }
+
+    let dict = PyDict::new(py);
+    for (info, builder) in columns.iter().zip(builders.into_iter()) {
+        dict.set_item(&info.name, builder.finish(py)?)?;
+    }
+    Ok(dict.into_any().unbind())
+}
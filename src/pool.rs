@@ -0,0 +1,247 @@
+//! Connection pooling: reuse `SharedClient`s across `TdsConnection::new`/
+//! `close` instead of opening a fresh `TcpStream` + TDS handshake every time.
+//!
+//! Modeled on the actix client-connector shape the request asks for: each
+//! distinct target (host/port/database/uid plus a hash of the credential)
+//! gets its own [`TdsPool`], with
+//! an `acquired` counter and a per-host counter enforcing `Max Pool Size`,
+//! and a FIFO queue of `oneshot` waiters parked when the limit is reached.
+//! Idle connections older than `Connection Lifetime` are dropped instead of
+//! reused, and every checkout is validated with a cheap `SELECT 1` so a
+//! socket the server (or a NAT) quietly closed gets discarded rather than
+//! handed back to a caller.
+
+use std::collections::{HashMap, VecDeque};
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use once_cell::sync::Lazy;
+use pyo3::prelude::*;
+use tokio::sync::oneshot;
+
+use crate::connection::SharedClient;
+
+#[derive(Clone, Debug)]
+pub struct PoolConfig {
+    pub max_pool_size: u32,
+    pub connection_lifetime: Duration,
+}
+
+impl Default for PoolConfig {
+    fn default() -> Self {
+        Self {
+            max_pool_size: 100,
+            // 0 == unlimited, matching SqlClient's `Connection Lifetime=0` default.
+            connection_lifetime: Duration::ZERO,
+        }
+    }
+}
+
+struct IdleConn {
+    client: SharedClient,
+    created_at: Instant,
+}
+
+/// A parked FIFO waiter. Carries `host` so that when a slot frees up, the
+/// freer can credit the per-host counter for the waiter it's handing the
+/// slot to, not just the host whose connection was released.
+struct Waiter {
+    host: String,
+    tx: oneshot::Sender<()>,
+}
+
+#[derive(Default)]
+struct PoolInner {
+    idle: VecDeque<IdleConn>,
+    acquired: u32,
+    acquired_per_host: HashMap<String, u32>,
+    waiters: VecDeque<Waiter>,
+}
+
+pub struct TdsPool {
+    config: PoolConfig,
+    inner: Mutex<PoolInner>,
+}
+
+static POOLS: Lazy<Mutex<HashMap<String, Arc<TdsPool>>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+impl TdsPool {
+    /// Get or create the pool for `key` (`TdsConnection::new` passes
+    /// `host:port/database/uid/<credential hash>` — one pool per distinct
+    /// connection identity, the same granularity
+    /// ADO.NET's connection pooling uses).
+    pub fn for_key(key: &str, config: PoolConfig) -> Arc<TdsPool> {
+        let mut pools = POOLS.lock().unwrap();
+        pools
+            .entry(key.to_string())
+            .or_insert_with(|| {
+                Arc::new(TdsPool {
+                    config,
+                    inner: Mutex::new(PoolInner::default()),
+                })
+            })
+            .clone()
+    }
+
+    /// Hand out a `SharedClient` for `host`, reusing a validated idle
+    /// connection when one is available, opening a fresh one via `connect`
+    /// when there's spare capacity, or parking FIFO behind other waiters
+    /// once the global/per-host `Max Pool Size` is reached. Returns the
+    /// client along with the `Instant` its underlying connection was
+    /// originally established, for `Connection Lifetime` bookkeeping at
+    /// [`Self::release`].
+    pub async fn acquire<F, Fut>(
+        self: &Arc<Self>,
+        host: &str,
+        connect: F,
+    ) -> PyResult<(SharedClient, Instant)>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = PyResult<SharedClient>>,
+    {
+        // Once a parked waiter is woken, `release_slot` has already
+        // credited `acquired`/`acquired_per_host` on its behalf — the slot
+        // was handed to it directly, not just freed for anyone to grab —
+        // so it must not re-enter the capacity check (it would see itself
+        // counted and wrongly conclude the pool is still full) or
+        // increment the counters a second time on an idle-connection hit.
+        let mut granted = false;
+        loop {
+            let candidate = {
+                let mut inner = self.inner.lock().unwrap();
+                inner.idle.pop_front()
+            };
+            if let Some(idle) = candidate {
+                let expired = !self.config.connection_lifetime.is_zero()
+                    && idle.created_at.elapsed() >= self.config.connection_lifetime;
+                if !expired && Self::validate(&idle.client).await {
+                    if !granted {
+                        let mut inner = self.inner.lock().unwrap();
+                        inner.acquired += 1;
+                        *inner.acquired_per_host.entry(host.to_string()).or_insert(0) += 1;
+                    }
+                    return Ok((idle.client, idle.created_at));
+                }
+                // Stale or dead: drop it and see if another idle entry works.
+                continue;
+            }
+
+            if granted {
+                let now = Instant::now();
+                return match connect().await {
+                    Ok(client) => Ok((client, now)),
+                    Err(e) => {
+                        self.release_slot(host);
+                        Err(e)
+                    }
+                };
+            }
+
+            let wait_rx = {
+                let mut inner = self.inner.lock().unwrap();
+                let per_host = *inner.acquired_per_host.get(host).unwrap_or(&0);
+                if inner.acquired < self.config.max_pool_size
+                    && per_host < self.config.max_pool_size
+                {
+                    inner.acquired += 1;
+                    *inner.acquired_per_host.entry(host.to_string()).or_insert(0) += 1;
+                    None
+                } else {
+                    let (tx, rx) = oneshot::channel();
+                    inner.waiters.push_back(Waiter {
+                        host: host.to_string(),
+                        tx,
+                    });
+                    Some(rx)
+                }
+            };
+
+            match wait_rx {
+                None => {
+                    let now = Instant::now();
+                    match connect().await {
+                        Ok(client) => return Ok((client, now)),
+                        Err(e) => {
+                            // The slot was reserved above on the assumption
+                            // connect() would succeed; give it back (and
+                            // wake a waiter) now that it didn't, or it's
+                            // leaked for good — nothing else ever calls
+                            // release() for a connection that never came
+                            // into being.
+                            self.release_slot(host);
+                            return Err(e);
+                        }
+                    }
+                }
+                Some(rx) => {
+                    // release_slot() already reserved a slot for us before
+                    // waking us; loop back to actually take it (an idle
+                    // connection if one's there, else connect() directly)
+                    // instead of re-racing other callers for it.
+                    let _ = rx.await;
+                    granted = true;
+                    continue;
+                }
+            }
+        }
+    }
+
+    /// Give back one `acquired`/`acquired_per_host` slot; if a FIFO waiter
+    /// is parked, the slot is handed to it directly — `acquired`/
+    /// `acquired_per_host` are credited for the waiter's host in the same
+    /// locked section the releasing host is debited in, rather than merely
+    /// notifying the waiter to go re-race for the slot it was waiting on
+    /// (which a newly arriving `acquire()` call could win first, starving
+    /// the queue). Doesn't touch the idle list. Factored out of
+    /// [`Self::release`] so the failed-`connect()` path in [`Self::acquire`]
+    /// can release (or hand off) the slot it reserved without fabricating
+    /// an `IdleConn` for a connection that was never established.
+    fn release_slot(&self, host: &str) {
+        let mut inner = self.inner.lock().unwrap();
+        inner.acquired = inner.acquired.saturating_sub(1);
+        if let Some(count) = inner.acquired_per_host.get_mut(host) {
+            *count = count.saturating_sub(1);
+        }
+        if let Some(waiter) = inner.waiters.pop_front() {
+            inner.acquired += 1;
+            *inner
+                .acquired_per_host
+                .entry(waiter.host.clone())
+                .or_insert(0) += 1;
+            let _ = waiter.tx.send(());
+        }
+    }
+
+    async fn validate(client: &SharedClient) -> bool {
+        let client = client.clone();
+        let ok = async move {
+            let mut c = client.lock().unwrap();
+            c.execute_raw("SELECT 1".to_string())
+                .await?
+                .into_results()
+                .await
+        }
+        .await;
+        ok.is_ok()
+    }
+
+    /// Return a checked-out connection. `created_at` is the `Instant` its
+    /// underlying socket was originally established (returned by
+    /// [`Self::acquire`]) — connections older than `Connection Lifetime` are
+    /// closed outright instead of going back on the idle list. Always hands
+    /// the freed slot straight to the next FIFO waiter, if any, via
+    /// [`Self::release_slot`].
+    pub fn release(&self, host: &str, client: SharedClient, created_at: Instant) {
+        let expired = !self.config.connection_lifetime.is_zero()
+            && created_at.elapsed() >= self.config.connection_lifetime;
+        if !expired {
+            self.inner
+                .lock()
+                .unwrap()
+                .idle
+                .push_back(IdleConn { client, created_at });
+        }
+        self.release_slot(host);
+    }
+}
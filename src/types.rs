@@ -1,17 +1,73 @@
 use chrono::{Datelike, NaiveDate, NaiveDateTime, NaiveTime, Timelike};
+use once_cell::sync::Lazy;
 use pyo3::prelude::*;
 use pyo3::types::{PyBool, PyBytes, PyFloat, PyInt, PyString};
 use std::cell::RefCell;
+use std::collections::HashMap;
+use std::sync::RwLock;
 use tabby::SqlValue;
 
 use crate::row_writer::CompactValue;
 
+/// Registry of user-supplied output converters, keyed by the SQL type code
+/// produced by [`column_type_to_sql_type`] (pyodbc's `add_output_converter`
+/// model). Checked by [`compact_value_to_py_converted`] ahead of the default
+/// conversion so callers can swap in e.g. a `pendulum.DateTime` builder for
+/// temporal columns without touching the fast path for everyone else.
+static OUTPUT_CONVERTERS: Lazy<RwLock<HashMap<i32, PyObject>>> =
+    Lazy::new(|| RwLock::new(HashMap::new()));
+
+/// Register `func` as the output converter for `sql_type`. `func` is called
+/// with the value `compact_value_to_py` would otherwise have returned, and
+/// its return value is surfaced to the caller instead.
+pub fn register_output_converter(sql_type: i32, func: PyObject) {
+    OUTPUT_CONVERTERS.write().unwrap().insert(sql_type, func);
+}
+
+/// Remove every registered output converter.
+pub fn clear_output_converters() {
+    OUTPUT_CONVERTERS.write().unwrap().clear();
+}
+
+/// Like [`compact_value_to_py`], but first checks whether `sql_type` has a
+/// registered output converter; if so, the default-converted value is handed
+/// to it and its result returned instead. Kept as a plain-defaults
+/// convenience wrapper alongside [`compact_value_to_py_converted_opts`] —
+/// every current caller needs a cursor's [`ConversionOptions`], so this one
+/// currently has none of its own.
+#[allow(dead_code)]
+pub fn compact_value_to_py_converted(
+    py: Python<'_>,
+    val: &CompactValue,
+    sql_type: i32,
+) -> PyResult<PyObject> {
+    compact_value_to_py_converted_opts(py, val, sql_type, &ConversionOptions::default())
+}
+
+/// Same as [`compact_value_to_py_converted`], but honors `opts` (see
+/// [`compact_value_to_py_opts`]) for the default conversion handed to the
+/// output converter.
+pub fn compact_value_to_py_converted_opts(
+    py: Python<'_>,
+    val: &CompactValue,
+    sql_type: i32,
+    opts: &ConversionOptions,
+) -> PyResult<PyObject> {
+    let converter = OUTPUT_CONVERTERS.read().unwrap().get(&sql_type).cloned();
+    let default = compact_value_to_py_opts(py, val, opts)?;
+    match converter {
+        Some(func) if !default.is_none(py) => func.call1(py, (default,)),
+        _ => Ok(default),
+    }
+}
+
 // Cached Python module/class references — avoids repeated py.import() per value.
 // Like pyodbc's static caches but thread-local for safety.
 thread_local! {
     static DATETIME_CACHE: RefCell<Option<DateTimeCache>> = const { RefCell::new(None) };
     static UUID_CACHE: RefCell<Option<PyObject>> = const { RefCell::new(None) };
     static DECIMAL_CACHE: RefCell<Option<PyObject>> = const { RefCell::new(None) };
+    static ZONEINFO_CACHE: RefCell<Option<PyObject>> = const { RefCell::new(None) };
 }
 
 struct DateTimeCache {
@@ -74,11 +130,204 @@ where
     })
 }
 
+fn with_zoneinfo_cls<F, R>(py: Python<'_>, f: F) -> PyResult<R>
+where
+    F: FnOnce(Python<'_>, &Bound<'_, PyAny>) -> PyResult<R>,
+{
+    ZONEINFO_CACHE.with(|cell| {
+        let mut opt = cell.borrow_mut();
+        if opt.is_none() {
+            *opt = Some(py.import("zoneinfo")?.getattr("ZoneInfo")?.unbind());
+        }
+        let bound = opt.as_ref().unwrap().bind(py);
+        f(py, bound)
+    })
+}
+
+/// Build a zone-aware `datetime` for IANA zone `tz_name` from plain components.
+/// Shared by the naive-DATETIME "interpret in session zone" path and the
+/// DATETIMEOFFSET "resolve the stored instant in session zone" path below.
+fn zoned_datetime(
+    py: Python<'_>,
+    cache: &DateTimeCache,
+    tz_name: &str,
+    year: i32,
+    month: u32,
+    day: u32,
+    hour: u32,
+    minute: u32,
+    second: u32,
+    micros: u32,
+) -> PyResult<PyObject> {
+    with_zoneinfo_cls(py, |_py, zoneinfo_cls| {
+        let tz = zoneinfo_cls.call1((tz_name,))?;
+        Ok(cache
+            .datetime_cls
+            .bind(py)
+            .call1((year, month, day, hour, minute, second, micros, tz))?
+            .unbind())
+    })
+}
+
+/// Controls how temporal values with sub-microsecond resolution (DATETIME2(7),
+/// TIME(7), DATETIMEOFFSET at scale 7) are surfaced to Python. `Microsecond`
+/// is the historical behavior: truncate to a stdlib `datetime`/`time`, which
+/// silently drops the 100-ns digit SQL Server stores. `IsoString` instead
+/// returns an ISO-8601 string with the full fractional precision available,
+/// at the cost of callers no longer getting a native `datetime` object back.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SubSecondMode {
+    #[default]
+    Microsecond,
+    IsoString,
+}
+
+/// What to do when a decoded `days`/`seconds_fragments`/`increments` value
+/// would build a date or time outside the range chrono can represent.
+/// Mirrors chrono's own preference for surfacing an `Err` over silently
+/// producing a bogus value.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OutOfRangePolicy {
+    /// Raise a Python `ValueError` naming the SQL type and offending value.
+    #[default]
+    Raise,
+    /// Map the value to `None`.
+    Null,
+    /// Pin the value to the nearest representable minimum/maximum.
+    Clamp,
+}
+
+/// Bundles the conversion knobs threaded through [`sql_value_to_py_opts`] and
+/// [`compact_value_to_py_opts`]. `Default` keeps `sub_second` at
+/// `SubSecondMode::Microsecond`, today's truncate-to-`datetime` behavior; for
+/// `out_of_range` it takes `OutOfRangePolicy::Raise` rather than silently
+/// producing a bogus value (see [`OutOfRangePolicy`]'s own doc comment), and
+/// `session_timezone` is unset, so naive `DateTime`/`DateTime2` and
+/// fixed-offset `DateTimeOffset` output is unchanged.
+#[derive(Clone, Debug, Default)]
+pub struct ConversionOptions {
+    pub sub_second: SubSecondMode,
+    pub out_of_range: OutOfRangePolicy,
+    /// An IANA zone name (e.g. `"America/Chicago"`). When set, naive
+    /// `DATETIME`/`DATETIME2` values are interpreted as being in this zone,
+    /// and `DATETIMEOFFSET` values are resolved to their wall-clock time in
+    /// this zone instead of being pinned to their stored UTC offset.
+    pub session_timezone: Option<String>,
+}
+
+/// Build a `NaiveTime` from a seconds-since-midnight + nanosecond remainder,
+/// honoring the caller's [`OutOfRangePolicy`]. Returns `Ok(None)` for the
+/// `Null` policy so callers can short-circuit to `py.None()`.
+fn time_from_midnight(
+    _py: Python<'_>,
+    sql_type: &str,
+    secs: u32,
+    nanos: u32,
+    policy: OutOfRangePolicy,
+) -> PyResult<Option<NaiveTime>> {
+    match NaiveTime::from_num_seconds_from_midnight_opt(secs, nanos) {
+        Some(t) => Ok(Some(t)),
+        None => match policy {
+            OutOfRangePolicy::Raise => Err(pyo3::exceptions::PyValueError::new_err(format!(
+                "{} time component out of range: {}s + {}ns",
+                sql_type, secs, nanos
+            ))),
+            OutOfRangePolicy::Null => Ok(None),
+            OutOfRangePolicy::Clamp => Ok(Some(
+                NaiveTime::from_num_seconds_from_midnight_opt(secs.min(86_399), 0)
+                    .unwrap_or_else(|| NaiveTime::from_hms_opt(23, 59, 59).unwrap()),
+            )),
+        },
+    }
+}
+
+/// Build a `NaiveDate` from a day offset against `base`, honoring the
+/// caller's [`OutOfRangePolicy`]. `chrono::NaiveDate` covers roughly
+/// +/-262,000 years so in practice only deliberately corrupt wire data
+/// triggers this.
+fn date_from_days(
+    sql_type: &str,
+    base: NaiveDate,
+    days: i64,
+    policy: OutOfRangePolicy,
+) -> PyResult<Option<NaiveDate>> {
+    match base.checked_add_signed(chrono::Duration::days(days)) {
+        Some(d) => Ok(Some(d)),
+        None => match policy {
+            OutOfRangePolicy::Raise => Err(pyo3::exceptions::PyValueError::new_err(format!(
+                "{} date component out of range: {} days from {}",
+                sql_type, days, base
+            ))),
+            OutOfRangePolicy::Null => Ok(None),
+            OutOfRangePolicy::Clamp => Ok(Some(if days < 0 {
+                NaiveDate::MIN
+            } else {
+                NaiveDate::MAX
+            })),
+        },
+    }
+}
+
+/// Format an ISO-8601 date/time string at up to 100-ns precision from a
+/// `micros_to_components`-style breakdown plus the nanosecond remainder.
+pub(crate) fn format_iso_nanos(
+    year: i32,
+    month: u32,
+    day: u32,
+    hour: u32,
+    minute: u32,
+    second: u32,
+    nanos_fraction: u32,
+    offset_minutes: Option<i16>,
+) -> String {
+    let mut s = format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}",
+        year, month, day, hour, minute, second
+    );
+    if nanos_fraction > 0 {
+        s.push_str(&format!(".{:07}", nanos_fraction / 100));
+    }
+    match offset_minutes {
+        Some(mins) => {
+            let sign = if mins >= 0 { '+' } else { '-' };
+            let abs = mins.unsigned_abs();
+            s.push_str(&format!("{}{:02}:{:02}", sign, abs / 60, abs % 60));
+        }
+        None => {}
+    }
+    s
+}
+
 /// Convert a tabby SqlValue to a Python object.
 /// Uses cached module references (pyodbc technique) to avoid repeated imports.
+///
+/// Unreachable in this crate today: the fetch path decodes straight into
+/// [`CompactValue`] via [`crate::row_writer::PyRowWriter`] (see its module
+/// doc comment — "no `SqlValue` enum") and never builds a `SqlValue` to pass
+/// here. `sub_second`/`session_timezone` are wired to real `_attrs_before`
+/// entries against that live path instead, in
+/// [`compact_value_to_py_opts`]/`connection::extract_conversion_options`.
+/// Kept (rather than deleted) for the 100-ns `DateTime2`/`DateTimeOffset`
+/// fidelity `CompactValue`'s pre-normalized-to-microseconds fields can't
+/// carry, should a future `RowWriter` hook expose the raw value.
 #[inline]
 #[allow(dead_code)]
 pub fn sql_value_to_py(py: Python<'_>, data: &SqlValue<'static>) -> PyResult<PyObject> {
+    sql_value_to_py_opts(py, data, &ConversionOptions::default())
+}
+
+/// Same as [`sql_value_to_py`], but lets the caller opt into [`SubSecondMode::IsoString`]
+/// for `DateTime2`/`Time`/`DateTimeOffset` values that carry more than microsecond
+/// precision on the wire, choose an [`OutOfRangePolicy`] for corrupt wire data, and
+/// attach `opts.session_timezone` (a `zoneinfo.ZoneInfo`-aware zone) to naive and
+/// offset temporal values instead of leaving them naive or pinned to a fixed offset.
+#[allow(dead_code)]
+pub fn sql_value_to_py_opts(
+    py: Python<'_>,
+    data: &SqlValue<'static>,
+    opts: &ConversionOptions,
+) -> PyResult<PyObject> {
+    let mode = opts.sub_second;
     match data {
         // Fast path: primitives — direct PyObject creation, no module imports
         SqlValue::Bit(Some(v)) => Ok(PyBool::new(py, *v).to_owned().into_any().unbind()),
@@ -128,12 +377,29 @@ pub fn sql_value_to_py(py: Python<'_>, data: &SqlValue<'static>) -> PyResult<PyO
             let total_ms = ticks * 1000 / 300;
             let secs = (total_ms / 1000) as u32;
             let micros = ((total_ms % 1000) * 1000) as u32;
-            let date = base + chrono::Duration::days(days);
-            let time = NaiveTime::from_num_seconds_from_midnight_opt(secs, micros * 1000)
-                .unwrap_or_default();
+            let Some(date) = date_from_days("DATETIME", base, days, opts.out_of_range)? else {
+                return Ok(py.None());
+            };
+            let Some(time) =
+                time_from_midnight(py, "DATETIME", secs, micros * 1000, opts.out_of_range)?
+            else {
+                return Ok(py.None());
+            };
             let ndt = NaiveDateTime::new(date, time);
-            with_datetime(py, |_py, cache| {
-                Ok(cache
+            with_datetime(py, |_py, cache| match &opts.session_timezone {
+                Some(tz_name) => zoned_datetime(
+                    py,
+                    cache,
+                    tz_name,
+                    ndt.year(),
+                    ndt.month(),
+                    ndt.day(),
+                    ndt.hour(),
+                    ndt.minute(),
+                    ndt.second(),
+                    micros,
+                ),
+                None => Ok(cache
                     .datetime_cls
                     .bind(py)
                     .call1((
@@ -145,7 +411,7 @@ pub fn sql_value_to_py(py: Python<'_>, data: &SqlValue<'static>) -> PyResult<PyO
                         ndt.second(),
                         micros,
                     ))?
-                    .unbind())
+                    .unbind()),
             })
         }
 
@@ -153,12 +419,30 @@ pub fn sql_value_to_py(py: Python<'_>, data: &SqlValue<'static>) -> PyResult<PyO
             let base = NaiveDate::from_ymd_opt(1900, 1, 1).unwrap();
             let days = dt.days() as i64;
             let mins = dt.seconds_fragments() as i64;
-            let date = base + chrono::Duration::days(days);
-            let time = NaiveTime::from_num_seconds_from_midnight_opt((mins * 60) as u32, 0)
-                .unwrap_or_default();
+            let Some(date) = date_from_days("SMALLDATETIME", base, days, opts.out_of_range)?
+            else {
+                return Ok(py.None());
+            };
+            let Some(time) =
+                time_from_midnight(py, "SMALLDATETIME", (mins * 60) as u32, 0, opts.out_of_range)?
+            else {
+                return Ok(py.None());
+            };
             let ndt = NaiveDateTime::new(date, time);
-            with_datetime(py, |_py, cache| {
-                Ok(cache
+            with_datetime(py, |_py, cache| match &opts.session_timezone {
+                Some(tz_name) => zoned_datetime(
+                    py,
+                    cache,
+                    tz_name,
+                    ndt.year(),
+                    ndt.month(),
+                    ndt.day(),
+                    ndt.hour(),
+                    ndt.minute(),
+                    ndt.second(),
+                    0,
+                ),
+                None => Ok(cache
                     .datetime_cls
                     .bind(py)
                     .call1((
@@ -170,7 +454,7 @@ pub fn sql_value_to_py(py: Python<'_>, data: &SqlValue<'static>) -> PyResult<PyO
                         ndt.second(),
                         0u32,
                     ))?
-                    .unbind())
+                    .unbind()),
             })
         }
 
@@ -178,16 +462,49 @@ pub fn sql_value_to_py(py: Python<'_>, data: &SqlValue<'static>) -> PyResult<PyO
             let d = dt.date();
             let t = dt.time();
             let base = NaiveDate::from_ymd_opt(1, 1, 1).unwrap();
-            let date = base + chrono::Duration::days(d.days() as i64);
+            let Some(date) = date_from_days("DATETIME2", base, d.days() as i64, opts.out_of_range)?
+            else {
+                return Ok(py.None());
+            };
             let nanos = t.increments() as u64 * 10u64.pow(9 - t.scale() as u32);
             let secs = (nanos / 1_000_000_000) as u32;
             let remaining_nanos = (nanos % 1_000_000_000) as u32;
             let micros = remaining_nanos / 1000;
-            let time = NaiveTime::from_num_seconds_from_midnight_opt(secs, remaining_nanos)
-                .unwrap_or_default();
+            let Some(time) =
+                time_from_midnight(py, "DATETIME2", secs, remaining_nanos, opts.out_of_range)?
+            else {
+                return Ok(py.None());
+            };
             let ndt = NaiveDateTime::new(date, time);
-            with_datetime(py, |_py, cache| {
-                Ok(cache
+
+            if mode == SubSecondMode::IsoString && t.scale() > 6 {
+                let s = format_iso_nanos(
+                    ndt.year(),
+                    ndt.month(),
+                    ndt.day(),
+                    ndt.hour(),
+                    ndt.minute(),
+                    ndt.second(),
+                    remaining_nanos,
+                    None,
+                );
+                return Ok(PyString::new(py, &s).into_any().unbind());
+            }
+
+            with_datetime(py, |_py, cache| match &opts.session_timezone {
+                Some(tz_name) => zoned_datetime(
+                    py,
+                    cache,
+                    tz_name,
+                    ndt.year(),
+                    ndt.month(),
+                    ndt.day(),
+                    ndt.hour(),
+                    ndt.minute(),
+                    ndt.second(),
+                    micros,
+                ),
+                None => Ok(cache
                     .datetime_cls
                     .bind(py)
                     .call1((
@@ -199,13 +516,16 @@ pub fn sql_value_to_py(py: Python<'_>, data: &SqlValue<'static>) -> PyResult<PyO
                         ndt.second(),
                         micros,
                     ))?
-                    .unbind())
+                    .unbind()),
             })
         }
 
         SqlValue::Date(Some(d)) => {
             let base = NaiveDate::from_ymd_opt(1, 1, 1).unwrap();
-            let date = base + chrono::Duration::days(d.days() as i64);
+            let Some(date) = date_from_days("DATE", base, d.days() as i64, opts.out_of_range)?
+            else {
+                return Ok(py.None());
+            };
             with_datetime(py, |_py, cache| {
                 Ok(cache
                     .date_cls
@@ -223,6 +543,15 @@ pub fn sql_value_to_py(py: Python<'_>, data: &SqlValue<'static>) -> PyResult<PyO
             let hour = secs / 3600;
             let minute = (secs % 3600) / 60;
             let second = secs % 60;
+
+            if mode == SubSecondMode::IsoString && t.scale() > 6 {
+                let s = format_iso_nanos(0, 1, 1, hour, minute, second, remaining_nanos, None);
+                // Drop the synthetic date prefix added by format_iso_nanos; callers
+                // of the Time arm only want the "HH:MM:SS.fffffff" portion.
+                let time_part = s.splitn(2, 'T').nth(1).unwrap_or(&s);
+                return Ok(PyString::new(py, time_part).into_any().unbind());
+            }
+
             with_datetime(py, |_py, cache| {
                 Ok(cache
                     .time_cls
@@ -236,33 +565,76 @@ pub fn sql_value_to_py(py: Python<'_>, data: &SqlValue<'static>) -> PyResult<PyO
             let d = dto.datetime2().date();
             let t = dto.datetime2().time();
             let base = NaiveDate::from_ymd_opt(1, 1, 1).unwrap();
-            let date = base + chrono::Duration::days(d.days() as i64);
+            let Some(date) =
+                date_from_days("DATETIMEOFFSET", base, d.days() as i64, opts.out_of_range)?
+            else {
+                return Ok(py.None());
+            };
             let nanos = t.increments() as u64 * 10u64.pow(9 - t.scale() as u32);
             let secs = (nanos / 1_000_000_000) as u32;
             let remaining_nanos = (nanos % 1_000_000_000) as u32;
             let micros = remaining_nanos / 1000;
-            let time = NaiveTime::from_num_seconds_from_midnight_opt(secs, remaining_nanos)
-                .unwrap_or_default();
+            let Some(time) =
+                time_from_midnight(py, "DATETIMEOFFSET", secs, remaining_nanos, opts.out_of_range)?
+            else {
+                return Ok(py.None());
+            };
             let utc_ndt = NaiveDateTime::new(date, time);
             let offset_mins = dto.offset() as i32;
             let local_ndt = utc_ndt + chrono::Duration::minutes(offset_mins as i64);
-            with_datetime(py, |_py, cache| {
-                let td = cache.timedelta_cls.bind(py).call1((0, offset_mins * 60))?;
-                let tz = cache.timezone_cls.bind(py).call1((td,))?;
-                Ok(cache
-                    .datetime_cls
-                    .bind(py)
-                    .call1((
-                        local_ndt.year(),
-                        local_ndt.month(),
-                        local_ndt.day(),
-                        local_ndt.hour(),
-                        local_ndt.minute(),
-                        local_ndt.second(),
+
+            if mode == SubSecondMode::IsoString && t.scale() > 6 {
+                let s = format_iso_nanos(
+                    local_ndt.year(),
+                    local_ndt.month(),
+                    local_ndt.day(),
+                    local_ndt.hour(),
+                    local_ndt.minute(),
+                    local_ndt.second(),
+                    remaining_nanos,
+                    Some(offset_mins as i16),
+                );
+                return Ok(PyString::new(py, &s).into_any().unbind());
+            }
+
+            with_datetime(py, |_py, cache| match &opts.session_timezone {
+                // Resolve the stored UTC instant's wall-clock time in the
+                // session zone, rather than pinning it to the stored offset.
+                Some(tz_name) => {
+                    let utc = cache.timezone_cls.bind(py).getattr("utc")?;
+                    let utc_dt = cache.datetime_cls.bind(py).call1((
+                        utc_ndt.year(),
+                        utc_ndt.month(),
+                        utc_ndt.day(),
+                        utc_ndt.hour(),
+                        utc_ndt.minute(),
+                        utc_ndt.second(),
                         micros,
-                        tz,
-                    ))?
-                    .unbind())
+                        utc,
+                    ))?;
+                    with_zoneinfo_cls(py, |_py, zoneinfo_cls| {
+                        let tz = zoneinfo_cls.call1((tz_name.as_str(),))?;
+                        Ok(utc_dt.call_method1("astimezone", (tz,))?.unbind())
+                    })
+                }
+                None => {
+                    let td = cache.timedelta_cls.bind(py).call1((0, offset_mins * 60))?;
+                    let tz = cache.timezone_cls.bind(py).call1((td,))?;
+                    Ok(cache
+                        .datetime_cls
+                        .bind(py)
+                        .call1((
+                            local_ndt.year(),
+                            local_ndt.month(),
+                            local_ndt.day(),
+                            local_ndt.hour(),
+                            local_ndt.minute(),
+                            local_ndt.second(),
+                            micros,
+                            tz,
+                        ))?
+                        .unbind())
+                }
             })
         }
 
@@ -277,6 +649,63 @@ pub fn sql_value_to_py(py: Python<'_>, data: &SqlValue<'static>) -> PyResult<PyO
 /// This is the fast path — no SqlValue enum, pre-normalized temporal values.
 #[inline]
 pub fn compact_value_to_py(py: Python<'_>, val: &CompactValue) -> PyResult<PyObject> {
+    compact_value_to_py_opts(py, val, &ConversionOptions::default())
+}
+
+/// Validate a decomposed `(year, month, day)` against Python's
+/// `datetime.MINYEAR..=MAXYEAR` (1..=9999), honoring `policy` the same way
+/// [`date_from_days`] does for the `SqlValue` path. Unlike chrono's
+/// `NaiveDate`, `compact_value_to_py_opts`'s calendar math (shared with
+/// [`micros_to_components`]) never fails to produce *a* year for any
+/// `i32`/`i64` input — but Python's `date`/`datetime` constructors reject
+/// anything outside `1..=9999`, which is the out-of-range case this crate
+/// can actually hit here (corrupt wire data well outside SQL Server's own
+/// `0001`-`9999` `DATE` range). Returns `Ok(None)` for the `Null` policy so
+/// callers can short-circuit to `py.None()`.
+fn check_year_range(
+    sql_type: &str,
+    year: i32,
+    month: u32,
+    day: u32,
+    policy: OutOfRangePolicy,
+) -> PyResult<Option<(i32, u32, u32)>> {
+    if (1..=9999).contains(&year) {
+        return Ok(Some((year, month, day)));
+    }
+    match policy {
+        OutOfRangePolicy::Raise => Err(pyo3::exceptions::PyValueError::new_err(format!(
+            "{} year out of range: {}",
+            sql_type, year
+        ))),
+        OutOfRangePolicy::Null => Ok(None),
+        OutOfRangePolicy::Clamp => Ok(Some(if year < 1 {
+            (1, 1, 1)
+        } else {
+            (9999, 12, 31)
+        })),
+    }
+}
+
+/// Same as [`compact_value_to_py`], but honors the caller's [`ConversionOptions`]:
+/// `sub_second` controls [`SubSecondMode::IsoString`] for `Time`, the only
+/// `CompactValue` temporal variant that still carries full nanosecond
+/// resolution (the others are pre-normalized to microseconds upstream and
+/// always come back as native `date`/`datetime` objects, whatever
+/// `sub_second` says — use
+/// [`sql_value_to_py_opts`] against the raw `SqlValue` for true 100-ns
+/// fidelity on `DateTime2`/`DateTimeOffset`), `session_timezone` attaches an
+/// IANA zone to naive `DateTime` and resolves `DateTimeOffset` to that
+/// zone's wall-clock time instead of its stored fixed offset, and
+/// `out_of_range` (via [`check_year_range`]) governs what happens when
+/// `Date`/`DateTime`/`DateTimeOffset` decode to a year outside what Python's
+/// `datetime` module accepts, instead of letting that surface as a bare
+/// `ValueError` from the constructor call with no SQL-type context.
+pub fn compact_value_to_py_opts(
+    py: Python<'_>,
+    val: &CompactValue,
+    opts: &ConversionOptions,
+) -> PyResult<PyObject> {
+    let mode = opts.sub_second;
     match val {
         CompactValue::Null => Ok(py.None()),
         CompactValue::Bool(v) => Ok(PyBool::new(py, *v).to_owned().into_any().unbind()),
@@ -307,6 +736,10 @@ pub fn compact_value_to_py(py: Python<'_>, val: &CompactValue) -> PyResult<PyObj
             let d = doy - (153 * mp + 2) / 5 + 1;
             let m = if mp < 10 { mp + 3 } else { mp - 9 };
             let year = if m <= 2 { y + 1 } else { y };
+            let Some((year, m, d)) = check_year_range("DATE", year, m, d, opts.out_of_range)?
+            else {
+                return Ok(py.None());
+            };
             with_datetime(py, |_py, cache| {
                 Ok(cache.date_cls.bind(py).call1((year, m, d))?.unbind())
             })
@@ -318,6 +751,13 @@ pub fn compact_value_to_py(py: Python<'_>, val: &CompactValue) -> PyResult<PyObj
             let hour = total_secs / 3600;
             let minute = (total_secs % 3600) / 60;
             let second = total_secs % 60;
+
+            if mode == SubSecondMode::IsoString && remaining_nanos % 1000 != 0 {
+                let s = format_iso_nanos(0, 1, 1, hour, minute, second, remaining_nanos, None);
+                let time_part = s.splitn(2, 'T').nth(1).unwrap_or(&s);
+                return Ok(PyString::new(py, time_part).into_any().unbind());
+            }
+
             with_datetime(py, |_py, cache| {
                 Ok(cache
                     .time_cls
@@ -329,19 +769,69 @@ pub fn compact_value_to_py(py: Python<'_>, val: &CompactValue) -> PyResult<PyObj
         CompactValue::DateTime(micros) => {
             let (year, month, day, hour, minute, second, remaining_micros) =
                 micros_to_components(*micros);
-            with_datetime(py, |_py, cache| {
-                Ok(cache
+            let Some((year, month, day)) =
+                check_year_range("DATETIME", year, month, day, opts.out_of_range)?
+            else {
+                return Ok(py.None());
+            };
+            with_datetime(py, |_py, cache| match &opts.session_timezone {
+                Some(tz_name) => zoned_datetime(
+                    py,
+                    cache,
+                    tz_name,
+                    year,
+                    month,
+                    day,
+                    hour,
+                    minute,
+                    second,
+                    remaining_micros,
+                ),
+                None => Ok(cache
                     .datetime_cls
                     .bind(py)
                     .call1((year, month, day, hour, minute, second, remaining_micros))?
-                    .unbind())
+                    .unbind()),
             })
         }
         CompactValue::DateTimeOffset(micros, offset_minutes) => {
+            if let Some(tz_name) = &opts.session_timezone {
+                // Resolve the stored UTC instant's wall-clock time in the
+                // session zone, rather than pinning it to the stored offset.
+                let (year, month, day, hour, minute, second, remaining_micros) =
+                    micros_to_components(*micros);
+                let Some((year, month, day)) =
+                    check_year_range("DATETIMEOFFSET", year, month, day, opts.out_of_range)?
+                else {
+                    return Ok(py.None());
+                };
+                return with_datetime(py, |_py, cache| {
+                    let utc = cache.timezone_cls.bind(py).getattr("utc")?;
+                    let utc_dt = cache.datetime_cls.bind(py).call1((
+                        year,
+                        month,
+                        day,
+                        hour,
+                        minute,
+                        second,
+                        remaining_micros,
+                        utc,
+                    ))?;
+                    with_zoneinfo_cls(py, |_py, zoneinfo_cls| {
+                        let tz = zoneinfo_cls.call1((tz_name.as_str(),))?;
+                        Ok(utc_dt.call_method1("astimezone", (tz,))?.unbind())
+                    })
+                });
+            }
             let offset_micros = (*offset_minutes as i64) * 60 * 1_000_000;
             let local_micros = micros + offset_micros;
             let (year, month, day, hour, minute, second, remaining_micros) =
                 micros_to_components(local_micros);
+            let Some((year, month, day)) =
+                check_year_range("DATETIMEOFFSET", year, month, day, opts.out_of_range)?
+            else {
+                return Ok(py.None());
+            };
             with_datetime(py, |_py, cache| {
                 let td = cache
                     .timedelta_cls
@@ -361,7 +851,7 @@ pub fn compact_value_to_py(py: Python<'_>, val: &CompactValue) -> PyResult<PyObj
 /// Decompose microseconds since Unix epoch into (year, month, day, hour, min, sec, micros).
 /// Pure arithmetic — no chrono allocations.
 #[inline]
-fn micros_to_components(micros: i64) -> (i32, u32, u32, u32, u32, u32, u32) {
+pub(crate) fn micros_to_components(micros: i64) -> (i32, u32, u32, u32, u32, u32, u32) {
     let total_secs = micros.div_euclid(1_000_000);
     let remaining_micros = micros.rem_euclid(1_000_000) as u32;
 
@@ -388,7 +878,7 @@ fn micros_to_components(micros: i64) -> (i32, u32, u32, u32, u32, u32, u32) {
 }
 
 /// Convert i128 + scale to decimal string like "-123.45"
-fn decimal_i128_to_string(value: i128, scale: u8) -> String {
+pub(crate) fn decimal_i128_to_string(value: i128, scale: u8) -> String {
     let negative = value < 0;
     let abs = value.unsigned_abs();
     let s = abs.to_string();
@@ -409,6 +899,97 @@ fn decimal_i128_to_string(value: i128, scale: u8) -> String {
     }
 }
 
+/// Infer the `sp_executesql` `@params` declaration for a Python value, e.g.
+/// `"NVARCHAR(MAX)"` for a `str`. Returns `None` for values with no clean,
+/// unambiguous SQL type (notably `None` — a bare `NULL` literal needs no
+/// declared type, and guessing one risks rejecting a real value the caller
+/// sends for that same parameter on a later call), signaling that the caller
+/// should fall back to [`py_to_sql_literal`]-based substitution instead.
+pub fn py_to_sql_type_decl(py: Python<'_>, param: &Bound<'_, PyAny>) -> PyResult<Option<String>> {
+    if param.is_none() {
+        return Ok(None);
+    }
+
+    if param.is_instance_of::<PyBool>() {
+        return Ok(Some("BIT".to_string()));
+    }
+
+    if param.is_instance_of::<PyInt>() {
+        return Ok(Some("BIGINT".to_string()));
+    }
+
+    if param.is_instance_of::<PyFloat>() {
+        return Ok(Some("FLOAT".to_string()));
+    }
+
+    let is_decimal = with_decimal_cls(py, |_py, cls| param.is_instance(cls))?;
+    if is_decimal {
+        return Ok(Some(decimal_type_decl_from_tuple(param)?));
+    }
+
+    let is_datetime = with_datetime(py, |_py, cache| {
+        param.is_instance(cache.datetime_cls.bind(py))
+    })?;
+    if is_datetime {
+        let tzinfo = param.getattr("tzinfo")?;
+        return Ok(Some(if tzinfo.is_none() {
+            "DATETIME2(7)".to_string()
+        } else {
+            "DATETIMEOFFSET(7)".to_string()
+        }));
+    }
+
+    let is_date = with_datetime(py, |_py, cache| param.is_instance(cache.date_cls.bind(py)))?;
+    if is_date {
+        return Ok(Some("DATE".to_string()));
+    }
+
+    let is_time = with_datetime(py, |_py, cache| param.is_instance(cache.time_cls.bind(py)))?;
+    if is_time {
+        return Ok(Some("TIME(7)".to_string()));
+    }
+
+    let is_uuid = with_uuid_cls(py, |_py, cls| param.is_instance(cls))?;
+    if is_uuid {
+        return Ok(Some("UNIQUEIDENTIFIER".to_string()));
+    }
+
+    if param.is_instance_of::<PyBytes>() || param.extract::<Vec<u8>>().is_ok() {
+        return Ok(Some("VARBINARY(MAX)".to_string()));
+    }
+
+    if param.is_instance_of::<PyString>() {
+        return Ok(Some("NVARCHAR(MAX)".to_string()));
+    }
+
+    if let Ok(n) = param.extract::<crate::NumericData>() {
+        let precision = n.precision.clamp(1, 38);
+        let scale = n.scale.clamp(0, precision);
+        return Ok(Some(format!("DECIMAL({},{})", precision, scale)));
+    }
+
+    // Unknown type — let the caller fall back to literal substitution.
+    Ok(None)
+}
+
+/// Derive a `DECIMAL(precision,scale)` declaration from a `decimal.Decimal`'s
+/// `as_tuple()` (sign, digits, exponent) so the `sp_executesql` parameter
+/// declaration can hold the value's actual fractional digits instead of a
+/// fixed guess, which would silently truncate the server-side bound value.
+/// Both dimensions are clamped to SQL Server's 38-digit DECIMAL maximum.
+fn decimal_type_decl_from_tuple(param: &Bound<'_, PyAny>) -> PyResult<String> {
+    let tuple = param.call_method0("as_tuple")?;
+    let digits_len = tuple.getattr("digits")?.len()? as i32;
+    let exponent = tuple.getattr("exponent")?.extract::<i32>().unwrap_or(0);
+    let scale = (-exponent).clamp(0, 38);
+    // A positive exponent (e.g. Decimal("1E+2") -> digits=(1,), exponent=2) means
+    // the value has trailing zeros not present in `digits`, so they must be
+    // added back into the precision or we'd declare a DECIMAL too narrow to
+    // hold the value itself (DECIMAL(1,0) for 100, which SQL Server rejects).
+    let precision = (digits_len + exponent.max(0)).max(scale).clamp(1, 38);
+    Ok(format!("DECIMAL({},{})", precision, scale))
+}
+
 /// Convert a Python parameter to a SQL literal string for substitution.
 /// Caches module lookups via thread-local storage.
 pub fn py_to_sql_literal(py: Python<'_>, param: &Bound<'_, PyAny>) -> PyResult<String> {
@@ -481,6 +1062,9 @@ pub fn py_to_sql_literal(py: Python<'_>, param: &Bound<'_, PyAny>) -> PyResult<S
     // String (check last since many types convert to string)
     if param.is_instance_of::<PyString>() {
         let v: String = param.extract()?;
+        if let Some(parts) = parse_iso8601_datetime(&v) {
+            return Ok(iso8601_to_literal(&parts));
+        }
         let escaped = v.replace('\'', "''");
         return Ok(format!("N'{}'", escaped));
     }
@@ -511,6 +1095,155 @@ pub fn py_to_sql_literal(py: Python<'_>, param: &Bound<'_, PyAny>) -> PyResult<S
     Ok(format!("N'{}'", escaped))
 }
 
+/// Components recognized by [`parse_iso8601_datetime`].
+struct Iso8601Parts {
+    year: i32,
+    month: u32,
+    day: u32,
+    hour: u32,
+    minute: u32,
+    second: u32,
+    /// Fractional-second digits as written (one or more), before padding.
+    frac: Option<String>,
+    /// Offset from UTC in minutes; `Z`/`z` parses as `Some(0)`.
+    offset_minutes: Option<i32>,
+}
+
+/// Hand-rolled scanner for the round-trip ISO-8601 forms chrono's `FromStr`
+/// accepts: `YYYY-MM-DD`, a space or `T` separator, `HH:MM:SS`, optional
+/// `.fffffffff` fractional seconds, and an optional `Z` or `±HH:MM` offset.
+/// Returns `None` for anything that doesn't match, so callers can fall back
+/// to treating the string as an ordinary `VARCHAR` literal.
+fn parse_iso8601_datetime(s: &str) -> Option<Iso8601Parts> {
+    let bytes = s.as_bytes();
+    if bytes.len() < 19 {
+        return None;
+    }
+    let digits = |range: std::ops::Range<usize>| range.clone().all(|i| bytes[i].is_ascii_digit());
+    if !digits(0..4) || bytes[4] != b'-' || !digits(5..7) || bytes[7] != b'-' || !digits(8..10) {
+        return None;
+    }
+    if !matches!(bytes[10], b' ' | b'T' | b't') {
+        return None;
+    }
+    if !digits(11..13) || bytes[13] != b':' || !digits(14..16) || bytes[16] != b':' || !digits(17..19)
+    {
+        return None;
+    }
+
+    let year: i32 = s[0..4].parse().ok()?;
+    let month: u32 = s[5..7].parse().ok()?;
+    let day: u32 = s[8..10].parse().ok()?;
+    let hour: u32 = s[11..13].parse().ok()?;
+    let minute: u32 = s[14..16].parse().ok()?;
+    let second: u32 = s[17..19].parse().ok()?;
+    if !(1..=9999).contains(&year) || !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+        return None;
+    }
+    if hour > 23 || minute > 59 || second > 59 {
+        return None;
+    }
+
+    let mut idx = 19;
+    let mut frac = None;
+    if bytes.get(idx) == Some(&b'.') {
+        let start = idx + 1;
+        let mut end = start;
+        while bytes.get(end).is_some_and(u8::is_ascii_digit) {
+            end += 1;
+        }
+        if end == start {
+            return None;
+        }
+        frac = Some(s[start..end].to_string());
+        idx = end;
+    }
+
+    let offset_minutes = match s.get(idx..) {
+        None | Some("") => None,
+        Some("Z") | Some("z") => Some(0),
+        Some(tail) => {
+            let tb = tail.as_bytes();
+            if tb.len() != 6 || !matches!(tb[0], b'+' | b'-') {
+                return None;
+            }
+            if !tb[1].is_ascii_digit()
+                || !tb[2].is_ascii_digit()
+                || tb[3] != b':'
+                || !tb[4].is_ascii_digit()
+                || !tb[5].is_ascii_digit()
+            {
+                return None;
+            }
+            let oh: i32 = tail[1..3].parse().ok()?;
+            let om: i32 = tail[4..6].parse().ok()?;
+            if oh > 14 || om > 59 {
+                return None;
+            }
+            let sign = if tb[0] == b'+' { 1 } else { -1 };
+            Some(sign * (oh * 60 + om))
+        }
+    };
+
+    Some(Iso8601Parts {
+        year,
+        month,
+        day,
+        hour,
+        minute,
+        second,
+        frac,
+        offset_minutes,
+    })
+}
+
+/// Render parsed ISO-8601 components as a typed SQL literal: a plain quoted
+/// string when there's no fraction or offset to lose to implicit VARCHAR
+/// conversion, and a `CAST(... AS DATETIME2(7)/DATETIMEOFFSET(7))` otherwise
+/// so the fractional digits and offset survive the round trip.
+fn iso8601_to_literal(parts: &Iso8601Parts) -> String {
+    let date_time = format!(
+        "{:04}-{:02}-{:02} {:02}:{:02}:{:02}",
+        parts.year, parts.month, parts.day, parts.hour, parts.minute, parts.second
+    );
+    let frac7 = parts.frac.as_ref().map(|f| {
+        let mut f = f.clone();
+        f.truncate(7);
+        while f.len() < 7 {
+            f.push('0');
+        }
+        f
+    });
+
+    match (&frac7, parts.offset_minutes) {
+        (Some(f), Some(mins)) => {
+            let sign = if mins >= 0 { '+' } else { '-' };
+            let abs = mins.unsigned_abs();
+            format!(
+                "CAST('{}.{}{}{:02}:{:02}' AS DATETIMEOFFSET(7))",
+                date_time,
+                f,
+                sign,
+                abs / 60,
+                abs % 60
+            )
+        }
+        (None, Some(mins)) => {
+            let sign = if mins >= 0 { '+' } else { '-' };
+            let abs = mins.unsigned_abs();
+            format!(
+                "CAST('{}{}{:02}:{:02}' AS DATETIMEOFFSET)",
+                date_time,
+                sign,
+                abs / 60,
+                abs % 60
+            )
+        }
+        (Some(f), None) => format!("CAST('{}.{}' AS DATETIME2(7))", date_time, f),
+        (None, None) => format!("'{}'", date_time),
+    }
+}
+
 fn datetime_to_literal(_py: Python<'_>, param: &Bound<'_, PyAny>) -> PyResult<String> {
     let year: i32 = param.getattr("year")?.extract()?;
     let month: u32 = param.getattr("month")?.extract()?;
@@ -585,6 +1318,45 @@ fn datetime_to_literal(_py: Python<'_>, param: &Bound<'_, PyAny>) -> PyResult<St
     }
 }
 
+/// Get the SQL type code for a `sys.types`-style base type name (e.g. the
+/// `system_type_name` column `sp_describe_first_result_set` returns, with any
+/// `(...)` length/precision suffix already stripped). Mirrors the CASE
+/// expressions `DDBCSQLColumns`/`DDBCSQLGetTypeInfo` build server-side, kept
+/// here so `TdsCursor::describe` can do the same mapping client-side without
+/// a round trip.
+pub fn sql_type_name_to_code(type_name: &str) -> i32 {
+    match type_name {
+        "int" => 4,
+        "bigint" => -5,
+        "smallint" => 5,
+        "tinyint" => -6,
+        "bit" => -7,
+        "float" => 6,
+        "real" => 7,
+        "decimal" => 3,
+        "numeric" => 2,
+        "money" => 3,
+        "smallmoney" => 3,
+        "char" => 1,
+        "varchar" => 12,
+        "text" => -1,
+        "nchar" => -8,
+        "nvarchar" => -9,
+        "ntext" => -10,
+        "binary" => -2,
+        "varbinary" => -3,
+        "image" => -4,
+        "datetime" | "smalldatetime" | "datetime2" => 93,
+        "date" => 91,
+        "time" => 92,
+        "datetimeoffset" => -155,
+        "uniqueidentifier" => -11,
+        "xml" => -152,
+        "sql_variant" => -150,
+        _ => 0, // SQL_UNKNOWN_TYPE
+    }
+}
+
 /// Get the SQL type code for a tabby column type
 pub fn column_type_to_sql_type(type_name: &str) -> i32 {
     match type_name {
@@ -613,3 +1385,40 @@ pub fn column_type_to_sql_type(type_name: &str) -> i32 {
         _ => 12, // SQL_VARCHAR default
     }
 }
+
+/// Inverse of [`sql_type_name_to_code`]: build a T-SQL type declaration (for
+/// a `DECLARE`) from an ODBC SQL type code plus `ParamInfo`'s `columnSize`/
+/// `decimalDigits`, for output/input-output RPC parameters whose type can't
+/// be inferred from a Python value the way [`py_to_sql_type_decl`] infers
+/// input parameters — an output-only parameter's `dataPtr` starts out `None`.
+/// Falls back to `SQL_VARIANT`, which accepts any scalar SQL Server can
+/// return, for codes this doesn't recognize.
+pub fn sql_type_decl_from_param(sql_type: i32, column_size: i64, decimal_digits: i32) -> String {
+    let size = if column_size > 0 { column_size } else { 1 };
+    match sql_type {
+        4 => "INT".to_string(),
+        -5 => "BIGINT".to_string(),
+        5 => "SMALLINT".to_string(),
+        -6 => "TINYINT".to_string(),
+        -7 => "BIT".to_string(),
+        6 | 8 => "FLOAT".to_string(),
+        7 => "REAL".to_string(),
+        2 | 3 => format!("DECIMAL({}, {})", size.max(1), decimal_digits.max(0)),
+        1 => format!("CHAR({})", size),
+        12 => format!("VARCHAR({})", size),
+        -1 => "TEXT".to_string(),
+        -8 => format!("NCHAR({})", size),
+        -9 => format!("NVARCHAR({})", size),
+        -10 => "NTEXT".to_string(),
+        -2 => format!("BINARY({})", size),
+        -3 => format!("VARBINARY({})", size),
+        -4 => "IMAGE".to_string(),
+        93 => "DATETIME2(7)".to_string(),
+        91 => "DATE".to_string(),
+        92 => "TIME(7)".to_string(),
+        -155 => "DATETIMEOFFSET(7)".to_string(),
+        -11 => "UNIQUEIDENTIFIER".to_string(),
+        -152 => "XML".to_string(),
+        _ => "SQL_VARIANT".to_string(),
+    }
+}